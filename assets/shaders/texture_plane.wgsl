@@ -0,0 +1,323 @@
+// Fragment shader for the textured plane: the standard unlit material plus a letterbox matte
+// for the parts of the plane that the fitted texture doesn't cover, A/B comparison, channel
+// isolation, and color space and colormap views.
+
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, apply_pbr_lighting, main_pass_post_lighting_processing},
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT,
+}
+
+struct TexturePlaneExtension {
+    // Texture UV rect (min.xy, max.xy) that shows the image
+    content_rect: vec4<f32>,
+    // Linear color of the matte; alpha 0 leaves the plane see-through
+    matte_color: vec4<f32>,
+    // ChannelView discriminant
+    channel_view: u32,
+    // CompareMode discriminant, with the split position (plane UV x) and onion skin weight
+    compare_mode: u32,
+    compare_split: f32,
+    compare_blend: f32,
+    // Linear multiplier matching the material base color, applied to texture B
+    exposure: f32,
+    // ColorSpace and Colormap discriminants, with the component shown (0 for all) and the
+    // channel range spread over the colormap
+    color_space: u32,
+    color_component: u32,
+    colormap: u32,
+    colormap_range: vec2<f32>,
+    // 1 when the base texture is sRGB-encoded
+    texture_srgb: u32,
+}
+
+const CHANNEL_VIEW_COLOR: u32 = 0u;
+const CHANNEL_VIEW_RED: u32 = 1u;
+const CHANNEL_VIEW_GREEN: u32 = 2u;
+const CHANNEL_VIEW_BLUE: u32 = 3u;
+const CHANNEL_VIEW_ALPHA: u32 = 4u;
+const CHANNEL_VIEW_ALPHA_OVER_CHECKER: u32 = 5u;
+const CHANNEL_VIEW_PREMULTIPLIED_OVER_CHECKER: u32 = 6u;
+
+const COLOR_SPACE_RGB: u32 = 0u;
+const COLOR_SPACE_GRAYSCALE: u32 = 1u;
+const COLOR_SPACE_HSV: u32 = 2u;
+const COLOR_SPACE_LAB: u32 = 3u;
+const COLOR_SPACE_YCBCR: u32 = 4u;
+
+const COLORMAP_NONE: u32 = 0u;
+const COLORMAP_VIRIDIS: u32 = 1u;
+const COLORMAP_MAGMA: u32 = 2u;
+const COLORMAP_TURBO: u32 = 3u;
+const COLORMAP_JET: u32 = 4u;
+
+const LUMA: vec3<f32> = vec3(0.2126, 0.7152, 0.0722);
+
+const CHECKER_SIZE: f32 = 8.0; // Screen pixels
+const CHECKER_DARK: f32 = 0.2;
+const CHECKER_LIGHT: f32 = 0.45;
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        return value / 12.92;
+    }
+    return pow((value + 0.055) / 1.055, 2.4);
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        return value * 12.92;
+    }
+    return 1.055 * pow(value, 1.0 / 2.4) - 0.055;
+}
+
+fn srgb_to_linear3(color: vec3<f32>) -> vec3<f32> {
+    return vec3(srgb_to_linear(color.r), srgb_to_linear(color.g), srgb_to_linear(color.b));
+}
+
+fn linear_to_srgb3(color: vec3<f32>) -> vec3<f32> {
+    return vec3(linear_to_srgb(color.r), linear_to_srgb(color.g), linear_to_srgb(color.b));
+}
+
+// Hue, saturation and value, all in [0, 1]
+fn rgb_to_hsv(color: vec3<f32>) -> vec3<f32> {
+    let value = max(color.r, max(color.g, color.b));
+    let chroma = value - min(color.r, min(color.g, color.b));
+    var hue = 0.0;
+    if chroma > 0.0 {
+        if value == color.r {
+            hue = (color.g - color.b) / chroma;
+        } else if value == color.g {
+            hue = (color.b - color.r) / chroma + 2.0;
+        } else {
+            hue = (color.r - color.g) / chroma + 4.0;
+        }
+        hue = fract(hue / 6.0 + 1.0);
+    }
+    return vec3(hue, select(0.0, chroma / value, value > 0.0), value);
+}
+
+fn lab_f(t: f32) -> f32 {
+    if t > 0.008856 {
+        return pow(t, 1.0 / 3.0);
+    }
+    return 7.787 * t + 16.0 / 116.0;
+}
+
+// CIE L*a*b* (D65) from linear RGB, with L* / 100 and a*, b* offset from [-128, 127] to [0, 1]
+fn linear_rgb_to_lab(color: vec3<f32>) -> vec3<f32> {
+    let rgb = max(color, vec3(0.0));
+    let xyz = vec3(
+        dot(rgb, vec3(0.4124, 0.3576, 0.1805)) / 0.95047,
+        dot(rgb, vec3(0.2126, 0.7152, 0.0722)),
+        dot(rgb, vec3(0.0193, 0.1192, 0.9505)) / 1.08883,
+    );
+    let f = vec3(lab_f(xyz.x), lab_f(xyz.y), lab_f(xyz.z));
+    let lab = vec3(116.0 * f.y - 16.0, 500.0 * (f.x - f.y), 200.0 * (f.y - f.z));
+    return vec3(lab.x / 100.0, (lab.y + 128.0) / 255.0, (lab.z + 128.0) / 255.0);
+}
+
+// Full-range BT.601, with the chroma components offset to [0, 1]
+fn rgb_to_ycbcr(color: vec3<f32>) -> vec3<f32> {
+    let y = dot(color, vec3(0.299, 0.587, 0.114));
+    return vec3(y, (color.b - y) * 0.564 + 0.5, (color.r - y) * 0.713 + 0.5);
+}
+
+// Polynomial fits of the matplotlib colormaps, returning sRGB-encoded colors
+fn viridis(t: f32) -> vec3<f32> {
+    let c0 = vec3(0.2777273272234177, 0.005407344544966578, 0.3340998053353061);
+    let c1 = vec3(0.1050930431085774, 1.404613529898575, 1.384590162594685);
+    let c2 = vec3(-0.3308618287255563, 0.214847559468213, 0.09509516302823659);
+    let c3 = vec3(-4.634230498983486, -5.799100973351585, -19.33244095627987);
+    let c4 = vec3(6.228269936347081, 14.17993336680509, 56.69055260068105);
+    let c5 = vec3(4.776384997670288, -13.74514537774601, -65.35303263337234);
+    let c6 = vec3(-5.435455855934631, 4.645852612178535, 26.3124352495832);
+    return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
+}
+
+fn magma(t: f32) -> vec3<f32> {
+    let c0 = vec3(-0.002136485053939582, -0.000749655052795221, -0.005386127855323933);
+    let c1 = vec3(0.2516605407371642, 0.6775232436837668, 2.494026599312351);
+    let c2 = vec3(8.353717279216625, -3.577719514958484, 0.3144679030132573);
+    let c3 = vec3(-27.66873308576866, 14.26473078096533, -13.64921318813922);
+    let c4 = vec3(52.17613981234068, -27.94360607168351, 12.94416944238394);
+    let c5 = vec3(-50.76852536473588, 29.04658282127291, 4.23415299384598);
+    let c6 = vec3(18.65570506591883, -11.48977351997711, -5.601961508734096);
+    return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
+}
+
+// Polynomial approximation of Google's Turbo
+fn turbo(t: f32) -> vec3<f32> {
+    let v4 = vec4(1.0, t, t * t, t * t * t);
+    let v2 = v4.zw * v4.z;
+    return vec3(
+        dot(v4, vec4(0.13572138, 4.61539260, -42.66032258, 132.13108234)) + dot(v2, vec2(-152.94239396, 59.28637943)),
+        dot(v4, vec4(0.09140261, 2.19418839, 4.84296658, -14.18503333)) + dot(v2, vec2(4.27729857, 2.82956604)),
+        dot(v4, vec4(0.10667330, 12.64194608, -60.58204836, 110.36276771)) + dot(v2, vec2(-89.90310912, 27.34824973)),
+    );
+}
+
+fn jet(t: f32) -> vec3<f32> {
+    return clamp(vec3(1.5) - abs(4.0 * t - vec3(3.0, 2.0, 1.0)), vec3(0.0), vec3(1.0));
+}
+
+fn apply_colormap(value: f32) -> vec3<f32> {
+    let range = texture_plane.colormap_range;
+    let t = clamp((value - range.x) / max(range.y - range.x, 1e-6), 0.0, 1.0);
+    var color: vec3<f32>;
+    switch texture_plane.colormap {
+        case COLORMAP_VIRIDIS: {
+            color = viridis(t);
+        }
+        case COLORMAP_MAGMA: {
+            color = magma(t);
+        }
+        case COLORMAP_TURBO: {
+            color = turbo(t);
+        }
+        default: {
+            color = jet(t);
+        }
+    }
+    return srgb_to_linear3(clamp(color, vec3(0.0), vec3(1.0)));
+}
+
+// Converts the (already channel-isolated) color to the selected color space, showing each
+// component's value as the on-screen level, then optionally colormaps the first one. sRGB
+// textures are converted from their stored values; Lab always starts from linear RGB.
+fn visualize(color: vec4<f32>) -> vec4<f32> {
+    let color_space = texture_plane.color_space;
+    let component = texture_plane.color_component;
+    if color_space == COLOR_SPACE_RGB && component == 0u && texture_plane.colormap == COLORMAP_NONE {
+        return color;
+    }
+
+    let srgb = texture_plane.texture_srgb != 0u;
+    let stored = select(color.rgb, linear_to_srgb3(color.rgb), srgb);
+    var values: vec3<f32>;
+    switch color_space {
+        case COLOR_SPACE_GRAYSCALE: {
+            values = vec3(dot(stored, LUMA));
+        }
+        case COLOR_SPACE_HSV: {
+            values = rgb_to_hsv(stored);
+        }
+        case COLOR_SPACE_LAB: {
+            values = linear_rgb_to_lab(color.rgb);
+        }
+        case COLOR_SPACE_YCBCR: {
+            values = rgb_to_ycbcr(stored);
+        }
+        default: {
+            values = stored;
+        }
+    }
+    if component > 0u && component <= 3u {
+        values = vec3(values[component - 1u]);
+    }
+
+    if texture_plane.colormap != COLORMAP_NONE {
+        return vec4(apply_colormap(values.x), color.a);
+    }
+    // RGB channels of linear textures keep their values, like the channel view
+    if color_space == COLOR_SPACE_RGB && !srgb {
+        return vec4(values, color.a);
+    }
+    return vec4(srgb_to_linear3(values), color.a);
+}
+
+// Combines texture A (already tinted by the material) with texture B sampled at the same UV
+fn compare(a: vec4<f32>, b: vec4<f32>, plane_uv: vec2<f32>) -> vec4<f32> {
+    switch texture_plane.compare_mode {
+        case COMPARE_MODE_SPLIT: {
+            return select(a, b, plane_uv.x > texture_plane.compare_split);
+        }
+        case COMPARE_MODE_ONION_SKIN: {
+            return mix(a, b, texture_plane.compare_blend);
+        }
+        case COMPARE_MODE_DIFFERENCE: {
+            return vec4(abs(a.rgb - b.rgb), max(a.a, b.a));
+        }
+        default: {
+            return a;
+        }
+    }
+}
+
+// Color channels are already linear, so showing them as gray reproduces their stored value on
+// screen. Alpha is stored as-is, so it's decoded first to display the same way.
+fn isolate_channel(color: vec4<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+    let cell = floor(frag_coord / CHECKER_SIZE);
+    let checker = select(CHECKER_DARK, CHECKER_LIGHT, (i32(cell.x + cell.y) & 1) == 0);
+    switch texture_plane.channel_view {
+        case CHANNEL_VIEW_RED: {
+            return vec4(vec3(color.r), 1.0);
+        }
+        case CHANNEL_VIEW_GREEN: {
+            return vec4(vec3(color.g), 1.0);
+        }
+        case CHANNEL_VIEW_BLUE: {
+            return vec4(vec3(color.b), 1.0);
+        }
+        case CHANNEL_VIEW_ALPHA: {
+            return vec4(vec3(srgb_to_linear(color.a)), 1.0);
+        }
+        case CHANNEL_VIEW_ALPHA_OVER_CHECKER: {
+            return vec4(mix(vec3(checker), color.rgb, color.a), 1.0);
+        }
+        case CHANNEL_VIEW_PREMULTIPLIED_OVER_CHECKER: {
+            return vec4(color.rgb + vec3(checker) * (1.0 - color.a), 1.0);
+        }
+        default: {
+            return color;
+        }
+    }
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100)
+var<uniform> texture_plane: TexturePlaneExtension;
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var compare_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var compare_sampler: sampler;
+
+const COMPARE_MODE_SPLIT: u32 = 1u;
+const COMPARE_MODE_ONION_SKIN: u32 = 2u;
+const COMPARE_MODE_DIFFERENCE: u32 = 3u;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+#ifdef VERTEX_UVS_A
+    let uv = (pbr_input.material.uv_transform * vec3(in.uv, 1.0)).xy;
+    let compare_color = textureSample(compare_texture, compare_sampler, uv) * vec4(vec3(texture_plane.exposure), 1.0);
+    pbr_input.material.base_color = compare(pbr_input.material.base_color, compare_color, in.uv);
+#endif
+
+    // Before alpha_discard, which forces opaque materials to alpha 1
+    pbr_input.material.base_color = isolate_channel(pbr_input.material.base_color, in.position.xy);
+    pbr_input.material.base_color = visualize(pbr_input.material.base_color);
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+#ifdef VERTEX_UVS_A
+    let inside = all(uv >= texture_plane.content_rect.xy) && all(uv <= texture_plane.content_rect.zw);
+    if !inside {
+        if texture_plane.matte_color.a <= 0.0 {
+            discard;
+        }
+        pbr_input.material.base_color = texture_plane.matte_color;
+    }
+#endif
+
+    var out: FragmentOutput;
+    if (pbr_input.material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u {
+        out.color = apply_pbr_lighting(pbr_input);
+    } else {
+        out.color = pbr_input.material.base_color;
+    }
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
@@ -2,6 +2,8 @@
 // Copyright (C) 2026 vecnode
 
 use bevy::prelude::*;
+use bevy::math::Affine2;
+use bevy::platform::collections::HashMap;
 
 #[derive(Component)]
 pub struct RightCamera;
@@ -20,16 +22,170 @@ pub struct GridLine;
 #[derive(Component)]
 pub struct TexturedPlane;
 
+// Current width and height of a textured plane in meters
+#[derive(Component)]
+pub struct PlaneSize(pub Vec2);
+
+// Pan, zoom and rotation of the texture on its plane, applied on top of the fit. Pan is in
+// meters from the plane center with y pointing down the texture, like UVs.
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct TextureViewTransform {
+    pub pan: Vec2,
+    pub zoom: f32,
+    pub rotation: f32,
+}
+
+impl Default for TextureViewTransform {
+    fn default() -> Self {
+        Self {
+            pan: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl TextureViewTransform {
+    // Where a point of the unmoved texture (meters from the plane center) ends up on the plane
+    pub fn placement(&self) -> Affine2 {
+        Affine2::from_scale_angle_translation(Vec2::splat(self.zoom), self.rotation, self.pan)
+    }
+
+    // Maps plane UVs to the UVs of the unmoved texture. Rotation happens in meters so that it
+    // doesn't shear on non-square planes.
+    pub fn uv_transform(&self, plane_size: Vec2) -> Affine2 {
+        let uv_to_meters = Affine2::from_scale_angle_translation(plane_size, 0.0, -0.5 * plane_size);
+        uv_to_meters.inverse() * self.placement().inverse() * uv_to_meters
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum TextureViewDrag {
+    Pan { last_point: Vec2 },
+    Rotate { start_angle: f32, start_rotation: f32 },
+    CompareSplit,
+}
+
+#[derive(Resource, Default)]
+pub struct TextureViewDragState {
+    pub active: Option<TextureViewDrag>,
+}
+
+// A texel picked for two-point scale calibration, with where it was clicked in the world
+#[derive(Clone, Copy)]
+pub struct CalibrationPoint {
+    pub pixel: Vec2,
+    pub world: Vec3,
+}
+
+// What a saved scale belongs to. Frames of an image sequence share one scale, saved next to the
+// first frame.
+#[derive(Clone, PartialEq)]
+pub enum ScaleOwner {
+    Texture(AssetId<Image>),
+    Sequence(std::path::PathBuf),
+}
+
+// Real-world scale of the current texture. Saved next to file-backed images so it is restored
+// the next time they are opened.
+#[derive(Resource)]
+pub struct ScaleCalibrationState {
+    pub pixels_per_meter: f32,
+    pub previous_pixels_per_meter: f32,
+    // Texture or sequence whose saved scale has already been looked up
+    pub checked_owner: Option<ScaleOwner>,
+    pub apply_requested: bool,
+    pub save_requested: bool,
+    pub picking: bool,
+    pub points: Vec<CalibrationPoint>,
+    pub distance: f32,
+}
+
+impl Default for ScaleCalibrationState {
+    fn default() -> Self {
+        Self {
+            pixels_per_meter: crate::constants::DEFAULT_PIXELS_PER_METER,
+            previous_pixels_per_meter: crate::constants::DEFAULT_PIXELS_PER_METER,
+            checked_owner: None,
+            apply_requested: false,
+            save_requested: false,
+            picking: false,
+            points: Vec::new(),
+            distance: 1.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PixelInspectorState {
+    pub enabled: bool,
+}
+
+impl Default for PixelInspectorState {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AspectRatio {
     Ratio16_9,
     Square,
+    Ratio4_3,
+    Ratio3_2,
+    Ratio21_9,
+    Ratio9_16,
+    MatchTexture,
+    Custom,
+}
+
+impl AspectRatio {
+    pub const ALL: [AspectRatio; 8] = [
+        AspectRatio::Ratio16_9,
+        AspectRatio::Square,
+        AspectRatio::Ratio4_3,
+        AspectRatio::Ratio3_2,
+        AspectRatio::Ratio21_9,
+        AspectRatio::Ratio9_16,
+        AspectRatio::MatchTexture,
+        AspectRatio::Custom,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AspectRatio::Ratio16_9 => "16:9",
+            AspectRatio::Square => "Square",
+            AspectRatio::Ratio4_3 => "4:3",
+            AspectRatio::Ratio3_2 => "3:2",
+            AspectRatio::Ratio21_9 => "21:9",
+            AspectRatio::Ratio9_16 => "9:16",
+            AspectRatio::MatchTexture => "Texture",
+            AspectRatio::Custom => "Custom",
+        }
+    }
+
+    // Width divided by height. Match texture falls back to square until the texture has loaded.
+    pub fn ratio(&self, custom: Vec2, texture_size: Option<Vec2>) -> f32 {
+        let (width, height) = match self {
+            AspectRatio::Ratio16_9 => (16.0, 9.0),
+            AspectRatio::Square => (1.0, 1.0),
+            AspectRatio::Ratio4_3 => (4.0, 3.0),
+            AspectRatio::Ratio3_2 => (3.0, 2.0),
+            AspectRatio::Ratio21_9 => (21.0, 9.0),
+            AspectRatio::Ratio9_16 => (9.0, 16.0),
+            AspectRatio::MatchTexture => texture_size.map_or((1.0, 1.0), |size| (size.x, size.y)),
+            AspectRatio::Custom => (custom.x, custom.y),
+        };
+        if width > 0.0 && height > 0.0 { width / height } else { 1.0 }
+    }
 }
 
 #[derive(Resource)]
 pub struct AspectRatioState {
     pub current: AspectRatio,
     pub previous: AspectRatio,
+    pub custom: Vec2,
+    pub previous_custom: Vec2,
 }
 
 impl Default for AspectRatioState {
@@ -37,6 +193,8 @@ impl Default for AspectRatioState {
         Self {
             current: AspectRatio::Square,
             previous: AspectRatio::Square,
+            custom: Vec2::new(5.0, 4.0),
+            previous_custom: Vec2::new(5.0, 4.0),
         }
     }
 }
@@ -45,12 +203,62 @@ impl Default for AspectRatioState {
 pub enum TextureMode {
     Normal,
     Stretch,
+    Fill,
+    Tile,
+    Center,
+    FitWidth,
+    FitHeight,
+}
+
+impl TextureMode {
+    pub const ALL: [TextureMode; 7] = [
+        TextureMode::Normal,
+        TextureMode::Stretch,
+        TextureMode::Fill,
+        TextureMode::Tile,
+        TextureMode::Center,
+        TextureMode::FitWidth,
+        TextureMode::FitHeight,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextureMode::Normal => "Preserve",
+            TextureMode::Stretch => "Stretch",
+            TextureMode::Fill => "Fill",
+            TextureMode::Tile => "Tile",
+            TextureMode::Center => "Center",
+            TextureMode::FitWidth => "Fit width",
+            TextureMode::FitHeight => "Fit height",
+        }
+    }
+}
+
+// What is shown on the parts of the plane that the texture doesn't cover
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct LetterboxMatte {
+    pub color: [u8; 3],
+    pub transparent: bool,
+}
+
+impl LetterboxMatte {
+    pub fn linear_color(&self) -> LinearRgba {
+        if self.transparent {
+            return LinearRgba::NONE;
+        }
+        let [r, g, b] = self.color;
+        Color::srgb_u8(r, g, b).to_linear()
+    }
 }
 
 #[derive(Resource)]
 pub struct TextureModeState {
     pub current: TextureMode,
     pub previous: TextureMode,
+    pub tile_repeat: Vec2,
+    pub previous_tile_repeat: Vec2,
+    pub matte: LetterboxMatte,
+    pub previous_matte: LetterboxMatte,
 }
 
 impl Default for TextureModeState {
@@ -58,10 +266,45 @@ impl Default for TextureModeState {
         Self {
             current: TextureMode::Stretch,
             previous: TextureMode::Stretch,
+            tile_repeat: Vec2::splat(2.0),
+            previous_tile_repeat: Vec2::splat(2.0),
+            matte: LetterboxMatte::default(),
+            previous_matte: LetterboxMatte::default(),
+        }
+    }
+}
+
+// Size and placement of the textured plane in meters. With fit_to_grid the size follows the
+// aspect ratio and grid depth (or the whole grid with fit_inside_grid); otherwise width and
+// height are set directly.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PlaneLayout {
+    pub fit_to_grid: bool,
+    pub fit_inside_grid: bool,
+    pub size: Vec2,
+    // World X/Z of the plane center
+    pub position: Vec2,
+    pub snap_to_grid: bool,
+}
+
+impl Default for PlaneLayout {
+    fn default() -> Self {
+        Self {
+            fit_to_grid: true,
+            fit_inside_grid: false,
+            size: Vec2::splat(10.0),
+            position: Vec2::ZERO,
+            snap_to_grid: false,
         }
     }
 }
 
+#[derive(Resource, Default)]
+pub struct PlaneLayoutState {
+    pub current: PlaneLayout,
+    pub previous: PlaneLayout,
+}
+
 #[derive(Resource)]
 pub struct GridState {
     pub size_x: i32,
@@ -79,4 +322,860 @@ impl Default for GridState {
             previous_size_z: 10,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Resource)]
+pub struct TextureSourceState {
+    pub path: String,
+    // Location on disk, when the texture came from a file that can be watched
+    pub file_path: Option<std::path::PathBuf>,
+    pub current: Option<Handle<Image>>,
+    // Output of the adjustment stack, shown in place of the source while any layer is enabled
+    pub processed: Option<Handle<Image>>,
+    // Last handle bound to the plane material
+    pub previous: Option<Handle<Image>>,
+}
+
+impl TextureSourceState {
+    pub fn with_path(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            file_path: None,
+            current: None,
+            processed: None,
+            previous: None,
+        }
+    }
+
+    pub fn displayed(&self) -> Option<&Handle<Image>> {
+        self.processed.as_ref().or(self.current.as_ref())
+    }
+
+    pub fn set_file(&mut self, file_path: &std::path::Path, handle: Handle<Image>) {
+        self.path = file_path.display().to_string();
+        self.file_path = Some(file_path.to_path_buf());
+        self.current = Some(handle);
+    }
+
+    pub fn set_asset(&mut self, asset_path: impl Into<String>, handle: Handle<Image>) {
+        self.path = asset_path.into();
+        self.file_path = asset_file_path(&self.path);
+        self.current = Some(handle);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn asset_file_path(asset_path: &str) -> Option<std::path::PathBuf> {
+    Some(
+        bevy::asset::io::file::FileAssetReader::get_base_path()
+            .join(crate::constants::ASSET_FOLDER)
+            .join(asset_path),
+    )
+}
+
+#[cfg(target_arch = "wasm32")]
+fn asset_file_path(_asset_path: &str) -> Option<std::path::PathBuf> {
+    None
+}
+
+impl Default for TextureSourceState {
+    fn default() -> Self {
+        Self::with_path(crate::constants::DEFAULT_TEXTURE_PATH)
+    }
+}
+
+#[derive(Resource)]
+pub struct ToastState {
+    pub message: Option<String>,
+    pub timer: Timer,
+}
+
+impl ToastState {
+    pub fn show_error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        warn!("{message}");
+        self.message = Some(message);
+        self.timer.reset();
+    }
+}
+
+impl Default for ToastState {
+    fn default() -> Self {
+        Self {
+            message: None,
+            timer: Timer::from_seconds(crate::constants::TOAST_DURATION_SECS, TimerMode::Once),
+        }
+    }
+}
+
+pub struct FileBrowserEntry {
+    pub name: String,
+    pub path: std::path::PathBuf,
+    pub is_dir: bool,
+}
+
+#[derive(Resource)]
+pub struct FileBrowserState {
+    pub open: bool,
+    pub current_dir: std::path::PathBuf,
+    pub listed_dir: Option<std::path::PathBuf>,
+    pub entries: Vec<FileBrowserEntry>,
+    pub error: Option<String>,
+    pub asset_path: String,
+    pub open_as_sequence: bool,
+}
+
+impl Default for FileBrowserState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            current_dir: std::path::PathBuf::from(crate::constants::FILE_BROWSER_START_DIR),
+            listed_dir: None,
+            entries: Vec::new(),
+            error: None,
+            asset_path: String::new(),
+            open_as_sequence: false,
+        }
+    }
+}
+
+pub struct TextureLibraryEntry {
+    pub label: String,
+    pub path: String,
+    pub file_path: Option<std::path::PathBuf>,
+    pub handle: Handle<Image>,
+}
+
+#[derive(Resource, Default)]
+pub struct TextureLibrary {
+    pub entries: Vec<TextureLibraryEntry>,
+}
+
+#[derive(Resource)]
+pub struct HotReloadState {
+    pub enabled: bool,
+    pub timer: Timer,
+    pub watched_path: Option<std::path::PathBuf>,
+    pub last_modified: Option<std::time::SystemTime>,
+}
+
+impl Default for HotReloadState {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timer: Timer::from_seconds(crate::constants::HOT_RELOAD_POLL_SECS, TimerMode::Repeating),
+            watched_path: None,
+            last_modified: None,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ImageSequenceState {
+    pub frames: Vec<std::path::PathBuf>,
+    // Decoded frames around the playhead; the rest are evicted
+    pub handles: Vec<Option<Handle<Image>>>,
+    // Frames being decoded off the main thread, by index
+    pub decoding: HashMap<usize, bevy::tasks::Task<Result<Image, String>>>,
+    pub failed: Vec<bool>,
+    pub current_frame: usize,
+    pub previous_frame: Option<usize>,
+    pub playing: bool,
+    pub looping: bool,
+    pub fps: f32,
+    pub elapsed: f32,
+}
+
+impl ImageSequenceState {
+    pub fn load(&mut self, frames: Vec<std::path::PathBuf>) {
+        self.handles = vec![None; frames.len()];
+        self.decoding.clear();
+        self.failed = vec![false; frames.len()];
+        self.frames = frames;
+        self.current_frame = 0;
+        self.previous_frame = None;
+        self.playing = false;
+        self.elapsed = 0.0;
+    }
+
+    pub fn clear(&mut self) {
+        self.load(Vec::new());
+    }
+
+    pub fn contains(&self, handle: &Handle<Image>) -> bool {
+        self.handles.iter().flatten().any(|frame| frame == handle)
+    }
+}
+
+impl Default for ImageSequenceState {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            handles: Vec::new(),
+            decoding: HashMap::default(),
+            failed: Vec::new(),
+            current_frame: 0,
+            previous_frame: None,
+            playing: false,
+            looping: true,
+            fps: crate::constants::SEQUENCE_DEFAULT_FPS,
+            elapsed: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct AtlasLayout {
+    pub enabled: bool,
+    pub columns: u32,
+    pub rows: u32,
+    pub padding: u32,
+    pub cell: u32,
+}
+
+impl AtlasLayout {
+    pub fn cell_count(&self) -> u32 {
+        (self.columns * self.rows).max(1)
+    }
+}
+
+impl Default for AtlasLayout {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            columns: 4,
+            rows: 4,
+            padding: 0,
+            cell: 0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct AtlasState {
+    pub current: AtlasLayout,
+    pub previous: AtlasLayout,
+    pub animate: bool,
+    pub fps: f32,
+    pub elapsed: f32,
+}
+
+impl Default for AtlasState {
+    fn default() -> Self {
+        Self {
+            current: AtlasLayout::default(),
+            previous: AtlasLayout::default(),
+            animate: false,
+            fps: crate::constants::SEQUENCE_DEFAULT_FPS,
+            elapsed: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorKind {
+    Checkerboard,
+    UvGradient,
+    Noise,
+    SolidColor,
+    TextLabel,
+}
+
+impl GeneratorKind {
+    pub const ALL: [GeneratorKind; 5] = [
+        GeneratorKind::Checkerboard,
+        GeneratorKind::UvGradient,
+        GeneratorKind::Noise,
+        GeneratorKind::SolidColor,
+        GeneratorKind::TextLabel,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GeneratorKind::Checkerboard => "Checkerboard",
+            GeneratorKind::UvGradient => "UV gradient",
+            GeneratorKind::Noise => "Perlin noise",
+            GeneratorKind::SolidColor => "Solid color",
+            GeneratorKind::TextLabel => "Text label",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct GeneratorParams {
+    pub kind: GeneratorKind,
+    pub width: u32,
+    pub height: u32,
+    pub color_a: [u8; 4],
+    pub color_b: [u8; 4],
+    pub checker_size: u32,
+    pub noise_scale: f32,
+    pub octaves: u32,
+    pub seed: u32,
+    pub text: String,
+    pub text_scale: u32,
+}
+
+impl Default for GeneratorParams {
+    fn default() -> Self {
+        Self {
+            kind: GeneratorKind::Checkerboard,
+            width: 512,
+            height: 512,
+            color_a: [230, 230, 230, 255],
+            color_b: [40, 40, 40, 255],
+            checker_size: 32,
+            noise_scale: 64.0,
+            octaves: 4,
+            seed: 1,
+            text: "TEST PATTERN".to_string(),
+            text_scale: 6,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GeneratorState {
+    pub open: bool,
+    pub current: GeneratorParams,
+    pub previous: Option<GeneratorParams>,
+    pub generate_requested: bool,
+    // Set while a generator control is being dragged; live updates wait for the release
+    pub dragging: bool,
+    pub handle: Option<Handle<Image>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct ToneMappingSettings {
+    pub exposure_ev: f32,
+    pub tonemapping: bevy::core_pipeline::tonemapping::Tonemapping,
+}
+
+impl Default for ToneMappingSettings {
+    fn default() -> Self {
+        // No tonemapping by default so LDR textures display exactly as in the image
+        Self {
+            exposure_ev: 0.0,
+            tonemapping: bevy::core_pipeline::tonemapping::Tonemapping::None,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ToneMappingState {
+    pub current: ToneMappingSettings,
+    pub previous: ToneMappingSettings,
+}
+
+// Which part of the texture the plane shows
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum ChannelView {
+    #[default]
+    Color = 0,
+    Red = 1,
+    Green = 2,
+    Blue = 3,
+    Alpha = 4,
+    AlphaOverChecker = 5,
+    PremultipliedOverChecker = 6,
+}
+
+impl ChannelView {
+    pub const ALL: [ChannelView; 7] = [
+        ChannelView::Color,
+        ChannelView::Red,
+        ChannelView::Green,
+        ChannelView::Blue,
+        ChannelView::Alpha,
+        ChannelView::AlphaOverChecker,
+        ChannelView::PremultipliedOverChecker,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChannelView::Color => "RGB",
+            ChannelView::Red => "Red",
+            ChannelView::Green => "Green",
+            ChannelView::Blue => "Blue",
+            ChannelView::Alpha => "Alpha",
+            ChannelView::AlphaOverChecker => "Straight alpha over checker",
+            ChannelView::PremultipliedOverChecker => "Premultiplied alpha over checker",
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ChannelViewState {
+    pub current: ChannelView,
+    pub previous: ChannelView,
+}
+
+// Color space the plane shows the texture in
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum ColorSpace {
+    #[default]
+    Rgb = 0,
+    Grayscale = 1,
+    Hsv = 2,
+    Lab = 3,
+    YCbCr = 4,
+}
+
+impl ColorSpace {
+    pub const ALL: [ColorSpace; 5] = [
+        ColorSpace::Rgb,
+        ColorSpace::Grayscale,
+        ColorSpace::Hsv,
+        ColorSpace::Lab,
+        ColorSpace::YCbCr,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorSpace::Rgb => "RGB",
+            ColorSpace::Grayscale => "Grayscale",
+            ColorSpace::Hsv => "HSV",
+            ColorSpace::Lab => "Lab",
+            ColorSpace::YCbCr => "YCbCr",
+        }
+    }
+
+    // Names of the three components, or None for single-channel spaces
+    pub fn components(&self) -> Option<[&'static str; 3]> {
+        match self {
+            ColorSpace::Rgb => Some(["R", "G", "B"]),
+            ColorSpace::Grayscale => None,
+            ColorSpace::Hsv => Some(["Hue", "Saturation", "Value"]),
+            ColorSpace::Lab => Some(["L*", "a*", "b*"]),
+            ColorSpace::YCbCr => Some(["Y", "Cb", "Cr"]),
+        }
+    }
+}
+
+// Maps a single channel to color
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum Colormap {
+    #[default]
+    None = 0,
+    Viridis = 1,
+    Magma = 2,
+    Turbo = 3,
+    Jet = 4,
+}
+
+impl Colormap {
+    pub const ALL: [Colormap; 5] = [Colormap::None, Colormap::Viridis, Colormap::Magma, Colormap::Turbo, Colormap::Jet];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Colormap::None => "None",
+            Colormap::Viridis => "Viridis",
+            Colormap::Magma => "Magma",
+            Colormap::Turbo => "Turbo",
+            Colormap::Jet => "Jet",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct ColorVisualization {
+    pub space: ColorSpace,
+    // 0 shows every component as RGB, 1-3 a single component as gray
+    pub component: u32,
+    pub colormap: Colormap,
+    // Channel values mapped to the ends of the colormap
+    pub range: Vec2,
+}
+
+impl Default for ColorVisualization {
+    fn default() -> Self {
+        Self {
+            space: ColorSpace::Rgb,
+            component: 0,
+            colormap: Colormap::None,
+            range: Vec2::new(0.0, 1.0),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ColorVisualizationState {
+    pub current: ColorVisualization,
+    pub previous: ColorVisualization,
+}
+
+// How texture B from the library is shown against the current texture A
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum CompareMode {
+    #[default]
+    Off = 0,
+    Split = 1,
+    OnionSkin = 2,
+    Difference = 3,
+}
+
+impl CompareMode {
+    pub const ALL: [CompareMode; 4] = [
+        CompareMode::Off,
+        CompareMode::Split,
+        CompareMode::OnionSkin,
+        CompareMode::Difference,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompareMode::Off => "Off",
+            CompareMode::Split => "Split",
+            CompareMode::OnionSkin => "Onion skin",
+            CompareMode::Difference => "Difference",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CompareSettings {
+    pub mode: CompareMode,
+    pub texture_b: Option<Handle<Image>>,
+    // Plane UV x of the split line; B is shown to its right
+    pub split: f32,
+    // Weight of B in the onion skin blend
+    pub blend: f32,
+}
+
+impl Default for CompareSettings {
+    fn default() -> Self {
+        Self {
+            mode: CompareMode::Off,
+            texture_b: None,
+            split: 0.5,
+            blend: 0.5,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CompareState {
+    pub current: CompareSettings,
+    pub previous: CompareSettings,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct TextureSampling {
+    pub nearest: bool,
+    pub mipmaps: bool,
+    pub anisotropy: u16,
+}
+
+impl Default for TextureSampling {
+    fn default() -> Self {
+        Self {
+            nearest: false,
+            mipmaps: false,
+            anisotropy: 1,
+        }
+    }
+}
+
+// Sampling settings remembered per texture. Textures seen for the first time inherit the
+// settings of the previous one, so switching images or playing a sequence keeps them.
+#[derive(Resource, Default)]
+pub struct TextureSamplingState {
+    pub per_texture: HashMap<AssetId<Image>, TextureSampling>,
+    pub last: TextureSampling,
+}
+
+impl TextureSamplingState {
+    pub fn settings_mut(&mut self, id: AssetId<Image>) -> &mut TextureSampling {
+        let fallback = self.last;
+        self.per_texture.entry(id).or_insert(fallback)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ClipboardState {
+    pub paste_count: u32,
+}
+
+// A single step of the adjustment stack. Parameters work on the image's stored values, so sRGB
+// images are adjusted in their encoded (perceptual) space.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Adjustment {
+    BrightnessContrast { brightness: f32, contrast: f32 },
+    Gamma { gamma: f32 },
+    Levels { black: f32, white: f32, gamma: f32 },
+    HueSaturation { hue: f32, saturation: f32 },
+    Invert,
+    Threshold { level: f32 },
+    Filter(ConvolutionFilter),
+}
+
+impl Adjustment {
+    pub const ALL: [Adjustment; 6] = [
+        Adjustment::BrightnessContrast { brightness: 0.0, contrast: 0.0 },
+        Adjustment::Gamma { gamma: 1.0 },
+        Adjustment::Levels { black: 0.0, white: 1.0, gamma: 1.0 },
+        Adjustment::HueSaturation { hue: 0.0, saturation: 0.0 },
+        Adjustment::Invert,
+        Adjustment::Threshold { level: 0.5 },
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Adjustment::BrightnessContrast { .. } => "Brightness / contrast",
+            Adjustment::Gamma { .. } => "Gamma",
+            Adjustment::Levels { .. } => "Levels",
+            Adjustment::HueSaturation { .. } => "Hue / saturation",
+            Adjustment::Invert => "Invert",
+            Adjustment::Threshold { .. } => "Threshold",
+            Adjustment::Filter(filter) => filter.label(),
+        }
+    }
+}
+
+// Square kernel of `size` x `size` weights, stored row-major
+#[derive(Clone, PartialEq, Debug)]
+pub struct CustomKernel {
+    pub size: usize,
+    pub weights: Vec<f32>,
+    // Divide by the sum of the weights (when it isn't zero) so flat areas keep their value
+    pub normalize: bool,
+    pub bias: f32,
+}
+
+impl CustomKernel {
+    pub fn identity() -> CustomKernel {
+        let mut weights = vec![0.0; 9];
+        weights[4] = 1.0;
+        CustomKernel { size: 3, weights, normalize: false, bias: 0.0 }
+    }
+
+    pub fn weight_mut(&mut self, x: usize, y: usize) -> &mut f32 {
+        &mut self.weights[y * self.size + x]
+    }
+
+    // Changes the size, keeping the weights centered
+    pub fn resize(&mut self, size: usize) {
+        let offset = (size as isize - self.size as isize) / 2;
+        let mut weights = vec![0.0; size * size];
+        for y in 0..size {
+            for x in 0..size {
+                let (source_x, source_y) = (x as isize - offset, y as isize - offset);
+                if (0..self.size as isize).contains(&source_x) && (0..self.size as isize).contains(&source_y) {
+                    weights[y * size + x] = self.weights[source_y as usize * self.size + source_x as usize];
+                }
+            }
+        }
+        self.size = size;
+        self.weights = weights;
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ConvolutionFilter {
+    BoxBlur { radius: u32 },
+    GaussianBlur { sigma: f32 },
+    Sharpen { amount: f32 },
+    Sobel,
+    Laplacian,
+    Emboss { strength: f32 },
+    Custom(CustomKernel),
+}
+
+impl ConvolutionFilter {
+    pub fn all() -> [ConvolutionFilter; 7] {
+        [
+            ConvolutionFilter::BoxBlur { radius: 1 },
+            ConvolutionFilter::GaussianBlur { sigma: 1.0 },
+            ConvolutionFilter::Sharpen { amount: 1.0 },
+            ConvolutionFilter::Sobel,
+            ConvolutionFilter::Laplacian,
+            ConvolutionFilter::Emboss { strength: 1.0 },
+            ConvolutionFilter::Custom(CustomKernel::identity()),
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConvolutionFilter::BoxBlur { .. } => "Box blur",
+            ConvolutionFilter::GaussianBlur { .. } => "Gaussian blur",
+            ConvolutionFilter::Sharpen { .. } => "Sharpen",
+            ConvolutionFilter::Sobel => "Sobel edges",
+            ConvolutionFilter::Laplacian => "Laplacian edges",
+            ConvolutionFilter::Emboss { .. } => "Emboss",
+            ConvolutionFilter::Custom(_) => "Custom kernel",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct AdjustmentLayer {
+    pub enabled: bool,
+    pub adjustment: Adjustment,
+}
+
+// Ordered stack applied top to bottom to a copy of the current texture. The source image is
+// never modified, so clearing the stack restores it.
+#[derive(Resource, Default)]
+pub struct AdjustmentStackState {
+    pub open: bool,
+    pub current: Vec<AdjustmentLayer>,
+    pub previous: Vec<AdjustmentLayer>,
+    // Set while a layer control is being dragged; the stack is recomputed on release
+    pub dragging: bool,
+    pub output: Option<Handle<Image>>,
+    // Source the output was last computed from
+    pub processed_source: Option<AssetId<Image>>,
+    // Stack being processed off the main thread; replacing it drops (cancels) the stale run
+    pub task: Option<bevy::tasks::Task<Image>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleFilter {
+    Nearest,
+    Bilinear,
+    #[default]
+    Lanczos,
+}
+
+impl ResampleFilter {
+    pub const ALL: [ResampleFilter; 3] = [ResampleFilter::Nearest, ResampleFilter::Bilinear, ResampleFilter::Lanczos];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResampleFilter::Nearest => "Nearest",
+            ResampleFilter::Bilinear => "Bilinear",
+            ResampleFilter::Lanczos => "Lanczos",
+        }
+    }
+}
+
+// Destructive edits that replace the source image with a new one
+#[derive(Clone, Copy, PartialEq)]
+pub enum ImageTransform {
+    Crop(URect),
+    Resize { size: UVec2, filter: ResampleFilter },
+    RotateClockwise,
+    RotateCounterClockwise,
+    Rotate180,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+#[derive(Resource)]
+pub struct ImageTransformState {
+    pub open: bool,
+    pub resize_size: UVec2,
+    pub keep_aspect: bool,
+    pub resample: ResampleFilter,
+    // Source the resize fields were filled in from
+    pub sized_for: Option<AssetId<Image>>,
+    // Dragging on the plane draws the crop rectangle instead of panning
+    pub drawing_crop: bool,
+    pub crop_anchor: Option<Vec2>,
+    // In source pixels
+    pub crop: Option<URect>,
+    pub pending: Option<ImageTransform>,
+    // Copy made by the last transform, overwritten while it stays the source
+    pub edited: Option<Handle<Image>>,
+    // Where the displayed image is written; filled in next to the source file
+    pub export_path: String,
+    pub export_requested: bool,
+    pub exported_to: Option<std::path::PathBuf>,
+}
+
+impl Default for ImageTransformState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            resize_size: UVec2::ONE,
+            keep_aspect: true,
+            resample: ResampleFilter::default(),
+            sized_for: None,
+            drawing_crop: false,
+            crop_anchor: None,
+            crop: None,
+            pending: None,
+            edited: None,
+            export_path: String::new(),
+            export_requested: false,
+            exported_to: None,
+        }
+    }
+}
+
+// Per-channel statistics of the displayed texture, in its stored encoding. Channels are red,
+// green, blue and luma for the histograms, red, green, blue and alpha for the rest.
+pub struct ImageStatistics {
+    pub size: UVec2,
+    pub histograms: [[u32; crate::constants::HISTOGRAM_BINS]; 4],
+    pub min: Vec4,
+    pub max: Vec4,
+    pub mean: Vec4,
+    pub std_dev: Vec4,
+    pub unique_colors: usize,
+}
+
+#[derive(Resource)]
+pub struct HistogramState {
+    pub open: bool,
+    pub log_scale: bool,
+    // Red, green, blue, luma
+    pub visible: [bool; 4],
+    pub statistics: Option<ImageStatistics>,
+    pub computed_for: Option<AssetId<Image>>,
+}
+
+impl Default for HistogramState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            log_scale: false,
+            visible: [true; 4],
+            statistics: None,
+            computed_for: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn growing_a_kernel_keeps_it_centered() {
+        let mut kernel = CustomKernel::identity();
+        kernel.resize(5);
+        assert_eq!(kernel.weights.len(), 25);
+        assert_eq!(kernel.weights.iter().position(|&weight| weight == 1.0), Some(12));
+        assert_eq!(kernel.weights.iter().sum::<f32>(), 1.0);
+    }
+
+    #[test]
+    fn shrinking_a_kernel_keeps_the_center() {
+        let mut kernel = CustomKernel {
+            size: 5,
+            weights: (0..25).map(|index| index as f32).collect(),
+            ..CustomKernel::identity()
+        };
+        kernel.resize(3);
+        assert_eq!(kernel.weights, vec![6.0, 7.0, 8.0, 11.0, 12.0, 13.0, 16.0, 17.0, 18.0]);
+    }
+
+    #[test]
+    fn resizing_up_and_back_is_lossless() {
+        let original = CustomKernel {
+            weights: (1..=9).map(|index| index as f32).collect(),
+            ..CustomKernel::identity()
+        };
+        let mut kernel = original.clone();
+        kernel.resize(7);
+        // The outer ring is dropped again when shrinking
+        *kernel.weight_mut(0, 0) = 5.0;
+        kernel.resize(3);
+        assert_eq!(kernel, original);
+    }
+}
@@ -20,4 +20,33 @@ pub const WORLD_BACKGROUND_COLOR: Color = Color::srgb(0.08, 0.08, 0.08); // Very
 
 // UI layout constants
 pub const EGUI_TOP_BAR_HEIGHT: f32 = 20.0;
-pub const EGUI_LEFT_PANEL_WIDTH: f32 = 200.0;
\ No newline at end of file
+pub const EGUI_LEFT_PANEL_WIDTH: f32 = 200.0;
+pub const LIBRARY_THUMBNAIL_SIZE: f32 = 84.0;
+
+// Texture constants
+pub const ASSET_FOLDER: &str = "assets";
+pub const DEFAULT_PIXELS_PER_METER: f32 = 100.0; // Texture scale until calibrated, also used by the Center fit mode
+pub const DEFAULT_TEXTURE_PATH: &str = "image_1.png"; // Relative to the assets folder
+pub const TEXTURE_PLANE_SHADER_PATH: &str = "shaders/texture_plane.wgsl"; // Relative to the assets folder
+pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "hdr", "exr"];
+pub const FILE_BROWSER_START_DIR: &str = ASSET_FOLDER;
+pub const HOT_RELOAD_POLL_SECS: f32 = 0.5;
+pub const SEQUENCE_DEFAULT_FPS: f32 = 24.0;
+pub const SEQUENCE_PREFETCH_FRAMES: usize = 8; // Decoded ahead of the playhead
+pub const SEQUENCE_KEEP_BEHIND_FRAMES: usize = 2; // Kept behind the playhead for short scrubs
+pub const SEQUENCE_MAX_DECODES: usize = 4; // Frames decoded at once
+pub const SCALE_SIDECAR_EXTENSION: &str = "scale"; // Saved as <image file name>.scale
+pub const PLANE_MIN_SIZE: f32 = 0.01; // Meters
+pub const ANISOTROPY_LEVELS: [u16; 5] = [1, 2, 4, 8, 16];
+pub const MAX_KERNEL_SIZE: usize = 7; // Largest custom convolution kernel
+pub const HISTOGRAM_BINS: usize = 256;
+
+// Texture view interaction constants
+pub const VIEW_ZOOM_STEP: f32 = 1.1; // Zoom factor per scroll line
+pub const VIEW_ZOOM_MIN: f32 = 0.05;
+pub const VIEW_ZOOM_MAX: f32 = 50.0;
+pub const ROTATE_HANDLE_OFFSET: f32 = 0.5; // Meters above the top edge of the texture
+pub const ROTATE_HANDLE_RADIUS: f32 = 7.0; // Screen pixels
+
+// UI feedback constants
+pub const TOAST_DURATION_SECS: f32 = 5.0;
@@ -3,18 +3,21 @@
 
 mod components;
 mod constants;
+mod materials;
 mod setup;
 mod systems;
 
 use bevy::prelude::*;
 use bevy::camera::Viewport;
+use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::log::LogPlugin;
+use bevy::render::view::Hdr;
 use bevy::window::WindowResolution;
 use bevy_egui::{EguiPlugin, EguiGlobalSettings, PrimaryEguiContext, EguiPrimaryContextPass};
 
 use setup::*;
 use systems::*;
-use components::EguiLayoutState;
+use components::{EguiLayoutState, TextureSourceState};
 use constants::WORLD_BACKGROUND_COLOR;
 
 fn main() {
@@ -36,6 +39,7 @@ fn main() {
                 })
         )
         .add_plugins(EguiPlugin::default())
+        .add_plugins(MaterialPlugin::<materials::TexturePlaneMaterial>::default())
         .insert_resource(EguiGlobalSettings {
             auto_create_primary_context: false,
             ..default()
@@ -45,11 +49,33 @@ fn main() {
         .init_resource::<components::GridState>()
         .init_resource::<components::AspectRatioState>()
         .init_resource::<components::TextureModeState>()
+        .init_resource::<components::ToastState>()
+        .init_resource::<components::FileBrowserState>()
+        .init_resource::<components::TextureLibrary>()
+        .init_resource::<components::HotReloadState>()
+        .init_resource::<components::ImageSequenceState>()
+        .init_resource::<components::AtlasState>()
+        .init_resource::<components::GeneratorState>()
+        .init_resource::<components::ToneMappingState>()
+        .init_resource::<components::ClipboardState>()
+        .init_resource::<components::TextureViewDragState>()
+        .init_resource::<components::TextureSamplingState>()
+        .init_resource::<components::PixelInspectorState>()
+        .init_resource::<components::PlaneLayoutState>()
+        .init_resource::<components::ScaleCalibrationState>()
+        .init_resource::<components::ChannelViewState>()
+        .init_resource::<components::CompareState>()
+        .init_resource::<components::AdjustmentStackState>()
+        .init_resource::<components::ImageTransformState>()
+        .init_resource::<components::HistogramState>()
+        .init_resource::<components::ColorVisualizationState>()
+        .insert_resource(startup_texture_source())
         .add_systems(
             Startup,
             (
                 spawn_grid,
                 spawn_textured_plane,
+                load_texture_source,
                 setup_camera_and_lights,
                 setup_split_screen_cameras,
             ),
@@ -57,17 +83,62 @@ fn main() {
         .add_systems(
             Update,
             (
-                update_grid_dimensions,
+                // After the refit, which compares the grid size against its previous value
+                update_grid_dimensions.after(update_texture_aspect_ratio),
+                (
+                    handle_dropped_files,
+                    hot_reload_texture,
+                    advance_image_sequence,
+                    animate_atlas_cells,
+                    apply_texture_generator,
+                    apply_image_transform,
+                )
+                    .before(prepare_display_texture),
+                prepare_display_texture.before(apply_adjustment_stack),
+                apply_adjustment_stack.before(apply_texture_sampling),
+                export_displayed_image.after(apply_adjustment_stack),
+                apply_texture_sampling.before(update_texture_aspect_ratio),
+                apply_texture_scale.after(prepare_display_texture).before(update_texture_aspect_ratio),
                 update_texture_aspect_ratio,
+                apply_tone_mapping,
+                apply_channel_view,
+                apply_color_visualization,
+                apply_compare_settings,
+                track_texture_library,
+                // After sampling, whose mip changes re-upload the displayed image
+                update_image_statistics.after(apply_texture_sampling),
+                tick_toast,
             ),
         )
         .add_systems(
             EguiPrimaryContextPass,
-            (egui_controls_ui, update_camera_viewports).chain(),
+            (
+                paste_clipboard_image,
+                egui_controls_ui,
+                texture_view_interaction,
+                calibration_picking,
+                crop_picking,
+                pixel_inspector_tooltip,
+                file_browser_window,
+                texture_generator_window,
+                adjustments_window,
+                image_transform_window,
+                histogram_window,
+                update_camera_viewports,
+            )
+                .chain(),
         )
         .run();
 }
 
+fn startup_texture_source() -> TextureSourceState {
+    // First command line argument overrides the default texture (asset path relative to assets/)
+    match std::env::args().nth(1) {
+        Some(path) => TextureSourceState::with_path(path),
+        None => TextureSourceState::default(),
+    }
+}
+
 fn setup_split_screen_cameras(
     mut commands: Commands,
     mut egui_global_settings: ResMut<EguiGlobalSettings>,
@@ -87,6 +158,9 @@ fn setup_split_screen_cameras(
             ..default()
         }),
         Transform::from_translation(crate::constants::CAMERA_TOP_POSITION).looking_at(Vec3::ZERO, Vec3::Z),
+        // HDR target so exposure above 1.0 reaches the tonemapper instead of clipping
+        Hdr,
+        Tonemapping::None,
         crate::components::RightCamera,
     ));
     
@@ -0,0 +1,83 @@
+// materials.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::pbr::{ExtendedMaterial, MaterialExtension};
+use bevy::render::render_resource::AsBindGroup;
+use bevy::shader::ShaderRef;
+use crate::components::{ChannelView, ColorSpace, Colormap, CompareMode};
+use crate::constants::TEXTURE_PLANE_SHADER_PATH;
+
+// Material of the TexturedPlane: the unlit StandardMaterial carries the texture and UV
+// transform, the extension adds what the standard shader can't do.
+pub type TexturePlaneMaterial = ExtendedMaterial<StandardMaterial, TexturePlaneExtension>;
+
+// The shader switches on the enum discriminants. Those enums are #[repr(u32)] with explicit
+// values that match the constants in texture_plane.wgsl.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+pub struct TexturePlaneExtension {
+    // Texture UV rect (min.xy, max.xy) covered by the image; the rest of the plane is matte
+    #[uniform(100)]
+    pub content_rect: Vec4,
+    #[uniform(100)]
+    pub matte_color: LinearRgba,
+    // ChannelView discriminant
+    #[uniform(100)]
+    pub channel_view: u32,
+    // CompareMode discriminant, with the split position and onion skin weight
+    #[uniform(100)]
+    pub compare_mode: u32,
+    #[uniform(100)]
+    pub compare_split: f32,
+    #[uniform(100)]
+    pub compare_blend: f32,
+    // Linear multiplier applied to both textures, mirroring the base color of the material
+    #[uniform(100)]
+    pub exposure: f32,
+    // ColorSpace and Colormap discriminants, with the component shown (0 for all) and the
+    // channel range spread over the colormap
+    #[uniform(100)]
+    pub color_space: u32,
+    #[uniform(100)]
+    pub color_component: u32,
+    #[uniform(100)]
+    pub colormap: u32,
+    #[uniform(100)]
+    pub colormap_range: Vec2,
+    // 1 when the base texture is sRGB-encoded, so conversions can start from the stored values
+    #[uniform(100)]
+    pub texture_srgb: u32,
+    #[texture(101)]
+    #[sampler(102)]
+    pub compare_texture: Option<Handle<Image>>,
+}
+
+impl TexturePlaneExtension {
+    pub const UNBOUNDED: Vec4 = Vec4::new(f32::MIN, f32::MIN, f32::MAX, f32::MAX);
+}
+
+impl Default for TexturePlaneExtension {
+    fn default() -> Self {
+        Self {
+            content_rect: Self::UNBOUNDED,
+            matte_color: LinearRgba::BLACK,
+            channel_view: ChannelView::Color as u32,
+            compare_mode: CompareMode::Off as u32,
+            compare_split: 0.5,
+            compare_blend: 0.5,
+            exposure: 1.0,
+            color_space: ColorSpace::Rgb as u32,
+            color_component: 0,
+            colormap: Colormap::None as u32,
+            colormap_range: Vec2::new(0.0, 1.0),
+            texture_srgb: 1,
+            compare_texture: None,
+        }
+    }
+}
+
+impl MaterialExtension for TexturePlaneExtension {
+    fn fragment_shader() -> ShaderRef {
+        TEXTURE_PLANE_SHADER_PATH.into()
+    }
+}
@@ -2,8 +2,9 @@
 // Copyright (C) 2026 vecnode
 
 use bevy::prelude::*;
-use crate::components::{GridLine, GridState, TexturedPlane};
+use crate::components::{GridLine, GridState, PlaneSize, TextureSourceState, TextureViewTransform, TexturedPlane};
 use crate::constants::*;
+use crate::materials::{TexturePlaneExtension, TexturePlaneMaterial};
 
 pub fn spawn_grid(
     mut commands: Commands,
@@ -60,16 +61,19 @@ pub fn spawn_grid_lines(
 pub fn spawn_textured_plane(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut plane_materials: ResMut<Assets<TexturePlaneMaterial>>,
     grid_state: Res<GridState>,
 ) {
     let size_x = grid_state.size_x as f32;
     let size_z = grid_state.size_z as f32;
     
     let plane_mesh = meshes.add(Rectangle::new(size_x, size_z));
-    let material = materials.add(StandardMaterial {
-        unlit: true,
-        ..default()
+    let material = plane_materials.add(TexturePlaneMaterial {
+        base: StandardMaterial {
+            unlit: true,
+            ..default()
+        },
+        extension: TexturePlaneExtension::default(),
     });
 
     commands.spawn((
@@ -78,9 +82,22 @@ pub fn spawn_textured_plane(
         Transform::from_translation(Vec3::new(0.0, 0.01, 0.0))
             .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2) * Quat::from_rotation_z(std::f32::consts::PI)),
         TexturedPlane,
+        PlaneSize(Vec2::new(size_x, size_z)),
+        TextureViewTransform::default(),
     ));
 }
 
+pub fn load_texture_source(
+    asset_server: Res<AssetServer>,
+    mut texture_source: ResMut<TextureSourceState>,
+) {
+    // The handle is applied to the plane material by update_texture_aspect_ratio,
+    // which also re-runs the fit once the image has finished loading.
+    let asset_path = texture_source.path.clone();
+    let handle = asset_server.load(asset_path.clone());
+    texture_source.set_asset(asset_path, handle);
+}
+
 pub fn setup_camera_and_lights(mut commands: Commands) {
     // Front light
     commands.spawn(DirectionalLight {
@@ -0,0 +1,250 @@
+// systems/adjustments.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool};
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{Adjustment, AdjustmentLayer, AdjustmentStackState, ConvolutionFilter, TextureSourceState};
+use crate::systems::filters::{apply_filter, filter_controls};
+use crate::systems::pixels::PixelBuffer;
+
+const LUMA: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+
+fn adjust_color(adjustment: &Adjustment, color: Vec3) -> Vec3 {
+    match *adjustment {
+        Adjustment::BrightnessContrast { brightness, contrast } => {
+            // Contrast pivots around mid grey; +1 approaches a hard threshold
+            let factor = (1.0 + contrast) / (1.0 - contrast).max(0.001);
+            (color + Vec3::splat(brightness - 0.5)) * factor + Vec3::splat(0.5)
+        }
+        Adjustment::Gamma { gamma } => color.max(Vec3::ZERO).powf(1.0 / gamma),
+        Adjustment::Levels { black, white, gamma } => {
+            let range = (white - black).max(0.001);
+            ((color - Vec3::splat(black)) / range).clamp(Vec3::ZERO, Vec3::ONE).powf(1.0 / gamma)
+        }
+        Adjustment::HueSaturation { hue, saturation } => {
+            let hsva = Hsva::from(Srgba::rgb(color.x, color.y, color.z));
+            let adjusted = hsva
+                .with_hue((hsva.hue + hue).rem_euclid(360.0))
+                .with_saturation((hsva.saturation * (1.0 + saturation)).clamp(0.0, 1.0));
+            let rgb = Srgba::from(adjusted);
+            Vec3::new(rgb.red, rgb.green, rgb.blue)
+        }
+        Adjustment::Invert => Vec3::ONE - color,
+        Adjustment::Threshold { level } => {
+            if color.dot(LUMA) >= level {
+                Vec3::ONE
+            } else {
+                Vec3::ZERO
+            }
+        }
+        // Filters need neighboring pixels, see apply_filter
+        Adjustment::Filter(_) => color,
+    }
+}
+
+fn process_stack(buffer: &mut PixelBuffer, layers: &[AdjustmentLayer]) {
+    for layer in layers.iter().filter(|layer| layer.enabled) {
+        if let Adjustment::Filter(filter) = &layer.adjustment {
+            apply_filter(buffer, filter);
+            continue;
+        }
+        for pixel in &mut buffer.pixels {
+            // Adjustments leave alpha untouched
+            *pixel = adjust_color(&layer.adjustment, pixel.truncate()).extend(pixel.w);
+        }
+    }
+}
+
+fn swap_in_output(
+    stack: &mut AdjustmentStackState,
+    texture_source: &mut TextureSourceState,
+    images: &mut Assets<Image>,
+    image: Image,
+) {
+    // Reuse the output asset so the material only rebinds when processing is switched on
+    match stack.output.clone() {
+        Some(output) => {
+            let _ = images.insert(output.id(), image);
+            texture_source.processed = Some(output);
+        }
+        None => {
+            let output = images.add(image);
+            stack.output = Some(output.clone());
+            texture_source.processed = Some(output);
+        }
+    }
+}
+
+// Recomputes the adjusted copy when the stack or the source image changes. The stack runs on the
+// async compute pool and the result is swapped in when it finishes. The copy keeps the source's
+// format, so the rest of the pipeline treats it like any other texture.
+pub fn apply_adjustment_stack(
+    mut stack: ResMut<AdjustmentStackState>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut images: ResMut<Assets<Image>>,
+    mut image_events: MessageReader<AssetEvent<Image>>,
+) {
+    if let Some(image) = stack.task.as_mut().and_then(check_ready) {
+        stack.task = None;
+        swap_in_output(&mut stack, &mut texture_source, &mut images, image);
+    }
+
+    let source_id = texture_source.current.as_ref().map(Handle::id);
+    let mut source_modified = false;
+    for event in image_events.read() {
+        if let Some(id) = source_id
+            && (event.is_loaded_with_dependencies(id) || event.is_modified(id))
+        {
+            source_modified = true;
+        }
+    }
+
+    let stack_changed = stack.current != stack.previous;
+    // Drags wait for the release instead of restarting the run every frame
+    if stack_changed && stack.dragging && !source_modified {
+        return;
+    }
+    if !stack_changed && !source_modified && stack.processed_source == source_id {
+        return;
+    }
+
+    if !stack.current.iter().any(|layer| layer.enabled) {
+        stack.previous = stack.current.clone();
+        stack.processed_source = source_id;
+        stack.task = None;
+        texture_source.processed = None;
+        return;
+    }
+
+    // Wait for the source to load before marking it as processed
+    let Some(source) = source_id.and_then(|id| images.get(id)) else {
+        stack.task = None;
+        texture_source.processed = None;
+        return;
+    };
+    stack.previous = stack.current.clone();
+    stack.processed_source = source_id;
+    let Some(mut buffer) = PixelBuffer::from_image(source) else {
+        warn!("Adjustments aren't supported for {:?} textures", source.texture_descriptor.format);
+        stack.task = None;
+        texture_source.processed = None;
+        return;
+    };
+
+    let layers = stack.current.clone();
+    stack.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+        process_stack(&mut buffer, &layers);
+        buffer.to_image()
+    }));
+}
+
+fn adjustment_controls(ui: &mut egui::Ui, adjustment: &mut Adjustment) {
+    match adjustment {
+        Adjustment::BrightnessContrast { brightness, contrast } => {
+            ui.add(egui::Slider::new(brightness, -1.0..=1.0).text("Brightness"));
+            ui.add(egui::Slider::new(contrast, -1.0..=1.0).text("Contrast"));
+        }
+        Adjustment::Gamma { gamma } => {
+            ui.add(egui::Slider::new(gamma, 0.1..=5.0).logarithmic(true).text("Gamma"));
+        }
+        Adjustment::Levels { black, white, gamma } => {
+            ui.add(egui::Slider::new(black, 0.0..=1.0).text("Black"));
+            ui.add(egui::Slider::new(white, 0.0..=1.0).text("White"));
+            ui.add(egui::Slider::new(gamma, 0.1..=5.0).logarithmic(true).text("Gamma"));
+        }
+        Adjustment::HueSaturation { hue, saturation } => {
+            ui.add(egui::Slider::new(hue, -180.0..=180.0).suffix("°").text("Hue"));
+            ui.add(egui::Slider::new(saturation, -1.0..=1.0).text("Saturation"));
+        }
+        Adjustment::Invert => {}
+        Adjustment::Threshold { level } => {
+            ui.add(egui::Slider::new(level, 0.0..=1.0).text("Level"));
+        }
+        Adjustment::Filter(filter) => filter_controls(ui, filter),
+    }
+}
+
+pub fn adjustments_window(mut contexts: EguiContexts, mut stack: ResMut<AdjustmentStackState>) {
+    if !stack.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = stack.open;
+    let mut move_up: Option<usize> = None;
+    let mut move_down: Option<usize> = None;
+    let mut remove: Option<usize> = None;
+
+    egui::Window::new("Adjustments")
+        .open(&mut open)
+        .default_width(280.0)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.menu_button("Add", |ui| {
+                    for adjustment in Adjustment::ALL {
+                        if ui.button(adjustment.label()).clicked() {
+                            stack.current.push(AdjustmentLayer { enabled: true, adjustment });
+                            ui.close();
+                        }
+                    }
+                    ui.separator();
+                    for filter in ConvolutionFilter::all() {
+                        if ui.button(filter.label()).clicked() {
+                            stack.current.push(AdjustmentLayer { enabled: true, adjustment: Adjustment::Filter(filter) });
+                            ui.close();
+                        }
+                    }
+                });
+                if ui.button("Reset").on_hover_text("Remove every layer and show the original").clicked() {
+                    stack.current.clear();
+                }
+            });
+            ui.separator();
+
+            if stack.current.is_empty() {
+                ui.label("No adjustments");
+            }
+            let layer_count = stack.current.len();
+            egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for (index, layer) in stack.current.iter_mut().enumerate() {
+                    ui.push_id(index, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut layer.enabled, layer.adjustment.label());
+                            if ui.add_enabled(index > 0, egui::Button::new("▲")).clicked() {
+                                move_up = Some(index);
+                            }
+                            if ui.add_enabled(index + 1 < layer_count, egui::Button::new("▼")).clicked() {
+                                move_down = Some(index);
+                            }
+                            if ui.button("✖").clicked() {
+                                remove = Some(index);
+                            }
+                        });
+                        ui.add_enabled_ui(layer.enabled, |ui| {
+                            adjustment_controls(ui, &mut layer.adjustment);
+                        });
+                        ui.separator();
+                    });
+                }
+            });
+        });
+    stack.open = open;
+    let dragging = open && ctx.is_using_pointer();
+    if stack.dragging != dragging {
+        stack.dragging = dragging;
+    }
+
+    if let Some(index) = move_up {
+        stack.current.swap(index, index - 1);
+    }
+    if let Some(index) = move_down {
+        stack.current.swap(index, index + 1);
+    }
+    if let Some(index) = remove {
+        stack.current.remove(index);
+    }
+}
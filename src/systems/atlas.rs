@@ -0,0 +1,82 @@
+// systems/atlas.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use crate::components::{AtlasLayout, AtlasState};
+
+// Pixel rect (min, size) of the selected cell. Padding is the gap between cells and
+// around the sheet border. None when the sheet isn't enabled or the layout doesn't fit.
+pub fn atlas_cell_rect(layout: &AtlasLayout, image_size: Vec2) -> Option<(Vec2, Vec2)> {
+    if !layout.enabled || layout.columns == 0 || layout.rows == 0 {
+        return None;
+    }
+    let grid = UVec2::new(layout.columns, layout.rows).as_vec2();
+    let padding = layout.padding as f32;
+    let cell_size = (image_size - padding * (grid + 1.0)) / grid;
+    if cell_size.x <= 0.0 || cell_size.y <= 0.0 {
+        return None;
+    }
+
+    let cell = layout.cell.min(layout.cell_count() - 1);
+    let column = cell % layout.columns;
+    let row = cell / layout.columns;
+    let cell_min = padding + UVec2::new(column, row).as_vec2() * (cell_size + padding);
+    Some((cell_min, cell_size))
+}
+
+pub fn animate_atlas_cells(time: Res<Time>, mut atlas_state: ResMut<AtlasState>) {
+    if !atlas_state.current.enabled || !atlas_state.animate || atlas_state.fps <= 0.0 {
+        return;
+    }
+
+    atlas_state.elapsed += time.delta_secs();
+    let frame_duration = 1.0 / atlas_state.fps;
+    let cell_count = atlas_state.current.cell_count();
+    while atlas_state.elapsed >= frame_duration {
+        atlas_state.elapsed -= frame_duration;
+        atlas_state.current.cell = (atlas_state.current.cell + 1) % cell_count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(columns: u32, rows: u32, padding: u32, cell: u32) -> AtlasLayout {
+        AtlasLayout { enabled: true, columns, rows, padding, cell }
+    }
+
+    #[test]
+    fn cells_are_laid_out_row_major() {
+        let image_size = Vec2::new(400.0, 200.0);
+        assert_eq!(atlas_cell_rect(&layout(4, 2, 0, 0), image_size), Some((Vec2::ZERO, Vec2::splat(100.0))));
+        assert_eq!(
+            atlas_cell_rect(&layout(4, 2, 0, 5), image_size),
+            Some((Vec2::new(100.0, 100.0), Vec2::splat(100.0)))
+        );
+    }
+
+    #[test]
+    fn padding_surrounds_every_cell() {
+        // 2 px border and gaps: the last cell ends 2 px before the sheet edge
+        let (cell_min, cell_size) = atlas_cell_rect(&layout(2, 2, 2, 3), Vec2::splat(100.0)).unwrap();
+        assert_eq!(cell_size, Vec2::splat(47.0));
+        assert_eq!(cell_min, Vec2::splat(51.0));
+        assert_eq!(cell_min + cell_size + 2.0, Vec2::splat(100.0));
+    }
+
+    #[test]
+    fn out_of_range_cells_clamp_to_the_last() {
+        let image_size = Vec2::splat(100.0);
+        assert_eq!(atlas_cell_rect(&layout(2, 2, 0, 9), image_size), atlas_cell_rect(&layout(2, 2, 0, 3), image_size));
+    }
+
+    #[test]
+    fn unusable_layouts_have_no_cell() {
+        let image_size = Vec2::splat(100.0);
+        assert_eq!(atlas_cell_rect(&AtlasLayout { enabled: false, ..layout(2, 2, 0, 0) }, image_size), None);
+        assert_eq!(atlas_cell_rect(&layout(0, 2, 0, 0), image_size), None);
+        // Padding wider than the sheet leaves no room for cells
+        assert_eq!(atlas_cell_rect(&layout(2, 2, 40, 0), image_size), None);
+    }
+}
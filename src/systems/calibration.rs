@@ -0,0 +1,160 @@
+// systems/calibration.rs
+// Copyright (C) 2026 vecnode
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{AtlasState, CalibrationPoint, EguiLayoutState, ImageSequenceState, ScaleOwner, PlaneLayoutState, PlaneSize, RightCamera, ScaleCalibrationState, TextureSourceState, ToastState, TexturedPlane};
+use crate::constants::{DEFAULT_PIXELS_PER_METER, SCALE_SIDECAR_EXTENSION};
+use crate::materials::TexturePlaneMaterial;
+use crate::systems::atlas::atlas_cell_rect;
+use crate::systems::picking::{cursor_in_viewport, pick_plane, plane_point_to_world, plane_uv_to_texture_pixel};
+
+fn sidecar_path(image_path: &Path) -> PathBuf {
+    let mut file_name = image_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(SCALE_SIDECAR_EXTENSION);
+    image_path.with_file_name(file_name)
+}
+
+// Sidecar files are plain `key = value` lines so they stay readable and hand-editable
+fn read_scale_sidecar(image_path: &Path) -> Option<f32> {
+    let contents = std::fs::read_to_string(sidecar_path(image_path)).ok()?;
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "pixels_per_meter" {
+            return None;
+        }
+        value.trim().parse::<f32>().ok().filter(|value| *value > 0.0)
+    })
+}
+
+fn write_scale_sidecar(image_path: &Path, pixels_per_meter: f32) -> Result<PathBuf, String> {
+    let path = sidecar_path(image_path);
+    std::fs::write(&path, format!("pixels_per_meter = {pixels_per_meter}\n"))
+        .map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+// Restores the saved scale when a texture is first shown, and sizes the plane so that one grid
+// meter covers pixels_per_meter texels
+pub fn apply_texture_scale(
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    atlas_state: Res<AtlasState>,
+    sequence_state: Res<ImageSequenceState>,
+    mut calibration: ResMut<ScaleCalibrationState>,
+    mut plane_layout_state: ResMut<PlaneLayoutState>,
+    mut toast_state: ResMut<ToastState>,
+) {
+    let Some(handle) = &texture_source.current else {
+        return;
+    };
+    let (owner, sidecar_image) = match sequence_state.frames.first() {
+        Some(first_frame) if sequence_state.contains(handle) => {
+            (ScaleOwner::Sequence(first_frame.clone()), Some(first_frame.as_path()))
+        }
+        _ => (ScaleOwner::Texture(handle.id()), texture_source.file_path.as_deref()),
+    };
+    if calibration.checked_owner.as_ref() != Some(&owner) {
+        calibration.checked_owner = Some(owner);
+        match sidecar_image.and_then(read_scale_sidecar) {
+            Some(pixels_per_meter) => {
+                calibration.pixels_per_meter = pixels_per_meter;
+                calibration.apply_requested = true;
+            }
+            // Don't carry the previous texture's scale over
+            None => calibration.pixels_per_meter = DEFAULT_PIXELS_PER_METER,
+        }
+    }
+
+    if calibration.save_requested {
+        calibration.save_requested = false;
+        if let Some(file_path) = sidecar_image {
+            match write_scale_sidecar(file_path, calibration.pixels_per_meter) {
+                Ok(path) => info!("Saved texture scale to {}", path.display()),
+                Err(error) => toast_state.show_error(format!("Could not save texture scale: {error}")),
+            }
+        }
+    }
+
+    if !calibration.apply_requested {
+        return;
+    }
+    // Wait for the image to load, since its size is needed
+    let Some(image) = images.get(handle) else {
+        return;
+    };
+    calibration.apply_requested = false;
+    let image_size = image.size().as_vec2();
+    let texture_size = atlas_cell_rect(&atlas_state.current, image_size).map_or(image_size, |(_, cell_size)| cell_size);
+    let layout = &mut plane_layout_state.current;
+    layout.fit_to_grid = false;
+    layout.size = texture_size / calibration.pixels_per_meter;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn calibration_picking(
+    mut contexts: EguiContexts,
+    window: Query<&Window>,
+    layout_state: Res<EguiLayoutState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut calibration: ResMut<ScaleCalibrationState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    materials: Res<Assets<TexturePlaneMaterial>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<RightCamera>>,
+    plane_query: Query<(&GlobalTransform, &PlaneSize, &MeshMaterial3d<TexturePlaneMaterial>), With<TexturedPlane>>,
+) {
+    if !calibration.picking && calibration.points.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let (Ok(window), Ok((camera, camera_transform))) = (window.single(), camera_query.single()) else {
+        return;
+    };
+
+    if calibration.picking
+        && mouse_buttons.just_pressed(MouseButton::Left)
+        && !ctx.is_pointer_over_area()
+        && let Some(cursor) = cursor_in_viewport(window, &layout_state)
+        && let Ok((plane_transform, plane_size, material_3d)) = plane_query.single()
+        && let Some(hit) = pick_plane(camera, camera_transform, cursor, plane_transform, plane_size.0)
+        && let Some(material) = materials.get(&material_3d.0)
+        && let Some(image) = texture_source.displayed().and_then(|handle| images.get(handle))
+        && let Some(pixel) = plane_uv_to_texture_pixel(material, image.size(), hit.uv).filter(|_| hit.is_inside())
+    {
+        calibration.points.push(CalibrationPoint {
+            pixel,
+            world: plane_point_to_world(plane_transform, hit.point),
+        });
+        if calibration.points.len() == 2 {
+            calibration.picking = false;
+        }
+    }
+
+    // Mark the picked points, and a line between them once both are set
+    let viewport = egui::Rect::from_min_max(
+        egui::pos2(layout_state.viewport_left, layout_state.viewport_top),
+        egui::pos2(layout_state.viewport_right, layout_state.viewport_bottom),
+    );
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("calibration_points")))
+        .with_clip_rect(viewport);
+    let stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 0));
+    let screen_points: Vec<egui::Pos2> = calibration
+        .points
+        .iter()
+        .filter_map(|point| camera.world_to_viewport(camera_transform, point.world).ok())
+        .map(|position| egui::pos2(position.x, position.y))
+        .collect();
+    if let [start, end] = screen_points[..] {
+        painter.line_segment([start, end], stroke);
+    }
+    for position in screen_points {
+        painter.circle_stroke(position, 5.0, stroke);
+    }
+}
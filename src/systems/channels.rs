@@ -0,0 +1,61 @@
+// systems/channels.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use crate::components::{ChannelViewState, ColorVisualizationState, TextureSourceState, TexturedPlane};
+use crate::materials::TexturePlaneMaterial;
+
+pub fn apply_channel_view(
+    mut channel_view_state: ResMut<ChannelViewState>,
+    mut materials: ResMut<Assets<TexturePlaneMaterial>>,
+    plane_query: Query<&MeshMaterial3d<TexturePlaneMaterial>, With<TexturedPlane>>,
+) {
+    if channel_view_state.current == channel_view_state.previous {
+        return;
+    }
+    channel_view_state.previous = channel_view_state.current;
+
+    for material_3d in plane_query.iter() {
+        if let Some(material) = materials.get_mut(&material_3d.0) {
+            material.extension.channel_view = channel_view_state.current as u32;
+        }
+    }
+}
+
+pub fn apply_color_visualization(
+    mut visualization_state: ResMut<ColorVisualizationState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    mut materials: ResMut<Assets<TexturePlaneMaterial>>,
+    plane_query: Query<&MeshMaterial3d<TexturePlaneMaterial>, With<TexturedPlane>>,
+) {
+    // Conversions start from the stored values, so the shader needs to know how they're encoded
+    let texture_srgb = texture_source
+        .displayed()
+        .and_then(|handle| images.get(handle))
+        .map(|image| image.texture_descriptor.format.is_srgb() as u32);
+    let visualization_changed = visualization_state.current != visualization_state.previous;
+    if visualization_changed {
+        visualization_state.previous = visualization_state.current;
+    }
+    let visualization = visualization_state.current;
+
+    for material_3d in plane_query.iter() {
+        let Some(material) = materials.get(&material_3d.0) else {
+            continue;
+        };
+        let srgb_changed = texture_srgb.is_some_and(|srgb| srgb != material.extension.texture_srgb);
+        if !visualization_changed && !srgb_changed {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(&material_3d.0) {
+            material.extension.color_space = visualization.space as u32;
+            material.extension.color_component = visualization.component;
+            material.extension.colormap = visualization.colormap as u32;
+            material.extension.colormap_range = visualization.range;
+            if let Some(srgb) = texture_srgb {
+                material.extension.texture_srgb = srgb;
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// systems/clipboard.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy_egui::{egui, EguiContexts};
+use base64::Engine;
+use crate::components::{ClipboardState, ImageSequenceState, TextureSourceState, ToastState};
+
+// Decodes "data:image/png;base64,..." into an image
+fn decode_data_url(data_url: &str) -> Result<Image, String> {
+    let (header, payload) = data_url
+        .trim()
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(|| "malformed data URL".to_string())?;
+    let mime_type = header
+        .strip_suffix(";base64")
+        .ok_or_else(|| "only base64 data URLs are supported".to_string())?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(payload.trim())
+        .map_err(|error| error.to_string())?;
+    Image::from_buffer(
+        &bytes,
+        ImageType::MimeType(mime_type),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .map_err(|error| error.to_string())
+}
+
+// None when the clipboard holds no image, e.g. just text
+#[cfg(not(target_arch = "wasm32"))]
+fn read_clipboard_image() -> Result<Option<Image>, String> {
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    let mut clipboard = arboard::Clipboard::new().map_err(|error| error.to_string())?;
+    let image_data = match clipboard.get_image() {
+        Ok(image_data) => image_data,
+        Err(arboard::Error::ContentNotAvailable) => return Ok(None),
+        Err(error) => return Err(error.to_string()),
+    };
+    Ok(Some(Image::new(
+        Extent3d {
+            width: image_data.width as u32,
+            height: image_data.height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        image_data.bytes.into_owned(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_clipboard_image() -> Result<Option<Image>, String> {
+    // Browsers only hand pasted text to the app, which is covered by the data URL path
+    Ok(None)
+}
+
+pub fn paste_clipboard_image(
+    mut contexts: EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut clipboard_state: ResMut<ClipboardState>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+    mut toast_state: ResMut<ToastState>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    // Leave pastes into text fields alone
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+
+    // Pasted text arrives as Paste on the web and as Text on desktop
+    let pasted_data_url = ctx.input(|input| {
+        input.events.iter().find_map(|event| match event {
+            egui::Event::Paste(text) | egui::Event::Text(text) if text.trim_start().starts_with("data:image/") => {
+                Some(text.clone())
+            }
+            _ => None,
+        })
+    });
+
+    let command_held = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let decoded = match pasted_data_url {
+        Some(data_url) => decode_data_url(&data_url).map(Some),
+        None if command_held && keys.just_pressed(KeyCode::KeyV) => read_clipboard_image(),
+        None => return,
+    };
+
+    match decoded {
+        Ok(Some(image)) => {
+            clipboard_state.paste_count += 1;
+            sequence_state.clear();
+            texture_source.current = Some(images.add(image));
+            texture_source.path = format!("Pasted image {}", clipboard_state.paste_count);
+            texture_source.file_path = None;
+        }
+        // Nothing to paste; plain text on the clipboard isn't an error
+        Ok(None) => {}
+        Err(error) => toast_state.show_error(format!("Could not paste image: {error}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_data_url(width: u32, height: u32) -> String {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::new(width, height)
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .unwrap();
+        format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(bytes.into_inner()))
+    }
+
+    #[test]
+    fn decodes_base64_png() {
+        let image = decode_data_url(&format!("  {}\n", png_data_url(3, 2))).unwrap();
+        assert_eq!(image.size(), UVec2::new(3, 2));
+    }
+
+    #[test]
+    fn rejects_malformed_urls() {
+        assert!(decode_data_url("image/png;base64,AAAA").is_err());
+        assert!(decode_data_url("data:image/png;base64").is_err());
+        // Percent-encoded payloads aren't supported
+        assert!(decode_data_url("data:image/png,AAAA").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_payloads() {
+        assert!(decode_data_url("data:image/png;base64,not*base64").is_err());
+        // Valid base64 that isn't a PNG
+        assert!(decode_data_url("data:image/png;base64,aGVsbG8=").is_err());
+    }
+}
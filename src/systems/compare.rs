@@ -0,0 +1,29 @@
+// systems/compare.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use crate::components::{CompareMode, CompareState, TexturedPlane};
+use crate::materials::TexturePlaneMaterial;
+
+pub fn apply_compare_settings(
+    mut compare_state: ResMut<CompareState>,
+    mut materials: ResMut<Assets<TexturePlaneMaterial>>,
+    plane_query: Query<&MeshMaterial3d<TexturePlaneMaterial>, With<TexturedPlane>>,
+) {
+    if compare_state.current == compare_state.previous {
+        return;
+    }
+    compare_state.previous = compare_state.current.clone();
+
+    // Without a B texture there is nothing to compare against
+    let settings = &compare_state.current;
+    let mode = if settings.texture_b.is_some() { settings.mode } else { CompareMode::Off };
+    for material_3d in plane_query.iter() {
+        if let Some(material) = materials.get_mut(&material_3d.0) {
+            material.extension.compare_mode = mode as u32;
+            material.extension.compare_split = settings.split;
+            material.extension.compare_blend = settings.blend;
+            material.extension.compare_texture = settings.texture_b.clone();
+        }
+    }
+}
@@ -2,18 +2,57 @@
 // Copyright (C) 2026 vecnode
 
 use bevy::prelude::*;
-use bevy_egui::{egui, EguiContexts};
-use crate::components::{AspectRatio, AspectRatioState, EguiLayoutState, GridState, TextureMode, TextureModeState};
-use crate::constants::{EGUI_LEFT_PANEL_WIDTH, EGUI_TOP_BAR_HEIGHT};
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::ecs::system::SystemParam;
+use bevy_egui::{egui, EguiContexts, EguiTextureHandle};
+use crate::components::{AdjustmentStackState, AspectRatio, AspectRatioState, AtlasState, ChannelView, ChannelViewState, ColorSpace, ColorVisualizationState, Colormap, CompareMode, CompareState, EguiLayoutState, FileBrowserState, GeneratorState, GridState, HistogramState, HotReloadState, ImageSequenceState, ImageTransformState, PixelInspectorState, PlaneLayoutState, RightCamera, ScaleCalibrationState, TextureLibrary, TextureMode, TextureModeState, TextureSamplingState, TextureSourceState, TextureViewTransform, TexturedPlane, ToastState, ToneMappingState};
+use crate::constants::{ANISOTROPY_LEVELS, EGUI_LEFT_PANEL_WIDTH, EGUI_TOP_BAR_HEIGHT, GRID_SPACING, LIBRARY_THUMBNAIL_SIZE, PLANE_MIN_SIZE, VIEW_ZOOM_MAX, VIEW_ZOOM_MIN};
+use crate::systems::atlas::atlas_cell_rect;
+use crate::systems::sampling::supports_mipmaps;
 
+// Further state edited from the panel, grouped to stay within the system parameter limit
+#[derive(SystemParam)]
+pub struct PanelParams<'w, 's> {
+    camera_projection: Query<'w, 's, &'static mut Projection, (With<Camera3d>, With<RightCamera>)>,
+    plane_views: Query<'w, 's, &'static mut TextureViewTransform, With<TexturedPlane>>,
+    sampling_state: ResMut<'w, TextureSamplingState>,
+    inspector_state: ResMut<'w, PixelInspectorState>,
+    plane_layout_state: ResMut<'w, PlaneLayoutState>,
+    calibration: ResMut<'w, ScaleCalibrationState>,
+    channel_view_state: ResMut<'w, ChannelViewState>,
+    compare_state: ResMut<'w, CompareState>,
+    adjustment_stack: ResMut<'w, AdjustmentStackState>,
+    transform_state: ResMut<'w, ImageTransformState>,
+    histogram_state: ResMut<'w, HistogramState>,
+    visualization_state: ResMut<'w, ColorVisualizationState>,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn egui_controls_ui(
     mut contexts: EguiContexts,
     mut layout_state: ResMut<EguiLayoutState>,
     mut grid_state: ResMut<GridState>,
     mut aspect_ratio_state: ResMut<AspectRatioState>,
     mut texture_mode_state: ResMut<TextureModeState>,
-    mut camera_projection: Query<&mut Projection, (With<Camera3d>, With<crate::components::RightCamera>)>,
+    toast_state: Res<ToastState>,
+    mut texture_source: ResMut<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    mut browser_state: ResMut<FileBrowserState>,
+    library: Res<TextureLibrary>,
+    mut hot_reload_state: ResMut<HotReloadState>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+    mut atlas_state: ResMut<AtlasState>,
+    mut generator_state: ResMut<GeneratorState>,
+    mut tone_mapping_state: ResMut<ToneMappingState>,
+    mut panel_params: PanelParams,
 ) {
+    // Register thumbnails before borrowing the context; weak handles since the library owns the images
+    let thumbnail_ids: Vec<egui::TextureId> = library
+        .entries
+        .iter()
+        .map(|entry| contexts.add_image(EguiTextureHandle::Weak(entry.handle.id())))
+        .collect();
+
     let Ok(ctx) = contexts.ctx_mut() else {
         return;
     };
@@ -42,10 +81,278 @@ pub fn egui_controls_ui(
                 ui.heading("Controls");
                 ui.separator();
 
+                if let Some(message) = &toast_state.message {
+                    ui.colored_label(ui.visuals().error_fg_color, message);
+                    ui.separator();
+                }
+
+                ui.label("Texture");
+                ui.horizontal_wrapped(|ui| {
+                    if ui.button("Open image…").clicked() {
+                        browser_state.open = true;
+                    }
+                    if ui.button("Generate…").clicked() {
+                        generator_state.open = true;
+                    }
+                    if ui.button("Adjust…").clicked() {
+                        panel_params.adjustment_stack.open = true;
+                    }
+                    if ui.button("Transform…").clicked() {
+                        panel_params.transform_state.open = true;
+                    }
+                    if ui.button("Histogram…").clicked() {
+                        panel_params.histogram_state.open = true;
+                    }
+                });
+                ui.label(egui::RichText::new(&texture_source.path).small())
+                    .on_hover_text(&texture_source.path);
+                if let Some(image) = texture_source.current.as_ref().and_then(|handle| images.get(handle)) {
+                    ui.label(format!("{} × {} px", image.width(), image.height()));
+                    ui.label(format!("{:?}", image.texture_descriptor.format));
+                } else if texture_source.current.is_some() {
+                    ui.label("Loading…");
+                }
+                if texture_source.file_path.is_some() {
+                    ui.checkbox(&mut hot_reload_state.enabled, "Reload on file change");
+                }
+
+                if !sequence_state.frames.is_empty() {
+                    ui.separator();
+                    ui.label(format!("Image sequence ({} frames)", sequence_state.frames.len()));
+                    ui.horizontal(|ui| {
+                        let last_frame = sequence_state.frames.len() - 1;
+                        if ui.button("⏮").on_hover_text("Previous frame").clicked() {
+                            sequence_state.playing = false;
+                            sequence_state.current_frame = sequence_state.current_frame.checked_sub(1).unwrap_or(last_frame);
+                        }
+                        let play_label = if sequence_state.playing { "⏸" } else { "▶" };
+                        if ui.button(play_label).clicked() {
+                            sequence_state.playing = !sequence_state.playing;
+                            sequence_state.elapsed = 0.0;
+                        }
+                        if ui.button("⏭").on_hover_text("Next frame").clicked() {
+                            sequence_state.playing = false;
+                            sequence_state.current_frame = if sequence_state.current_frame < last_frame {
+                                sequence_state.current_frame + 1
+                            } else {
+                                0
+                            };
+                        }
+                        if ui.button("✖").on_hover_text("Close sequence").clicked() {
+                            sequence_state.clear();
+                        }
+                    });
+                    if !sequence_state.frames.is_empty() {
+                        let last_frame = sequence_state.frames.len() - 1;
+                        ui.add(egui::Slider::new(&mut sequence_state.current_frame, 0..=last_frame).text("Frame"));
+                        ui.add(egui::Slider::new(&mut sequence_state.fps, 1.0..=60.0).text("FPS").step_by(1.0));
+                        ui.checkbox(&mut sequence_state.looping, "Loop");
+                    }
+                }
+
+                if !library.entries.is_empty() {
+                    ui.separator();
+                    ui.label("Texture library");
+                    let mut selected_entry = None;
+                    let mut compare_entry = None;
+                    ui.horizontal_wrapped(|ui| {
+                        for (index, (entry, texture_id)) in library.entries.iter().zip(&thumbnail_ids).enumerate() {
+                            let Some(image) = images.get(&entry.handle) else {
+                                continue;
+                            };
+                            // Fit the thumbnail inside a square cell, preserving the image aspect
+                            let image_size = egui::vec2(image.width() as f32, image.height() as f32);
+                            let thumbnail_size = image_size * (LIBRARY_THUMBNAIL_SIZE / image_size.max_elem());
+                            let is_current = texture_source.current.as_ref() == Some(&entry.handle);
+                            let thumbnail = egui::Image::new(egui::load::SizedTexture::new(*texture_id, thumbnail_size));
+                            let response = ui
+                                .add(egui::Button::image(thumbnail).selected(is_current))
+                                .on_hover_text(format!("{}\nRight-click to compare against", entry.label));
+                            if response.clicked() {
+                                selected_entry = Some(index);
+                            }
+                            if response.secondary_clicked() {
+                                compare_entry = Some(index);
+                            }
+                        }
+                    });
+                    if let Some(index) = selected_entry {
+                        let entry = &library.entries[index];
+                        sequence_state.clear();
+                        texture_source.current = Some(entry.handle.clone());
+                        texture_source.path = entry.path.clone();
+                        texture_source.file_path = entry.file_path.clone();
+                    }
+                    let compare = &mut panel_params.compare_state.current;
+                    if let Some(index) = compare_entry {
+                        compare.texture_b = Some(library.entries[index].handle.clone());
+                        if compare.mode == CompareMode::Off {
+                            compare.mode = CompareMode::Split;
+                        }
+                    }
+
+                    ui.separator();
+                    ui.label("Compare");
+                    ui.horizontal_wrapped(|ui| {
+                        for mode in CompareMode::ALL {
+                            ui.selectable_value(&mut compare.mode, mode, mode.label());
+                        }
+                    });
+                    if compare.mode != CompareMode::Off {
+                        let b_label = library
+                            .entries
+                            .iter()
+                            .find(|entry| Some(&entry.handle) == compare.texture_b.as_ref())
+                            .map_or("Choose…", |entry| entry.label.as_str());
+                        egui::ComboBox::from_label("B")
+                            .selected_text(b_label)
+                            .show_ui(ui, |ui| {
+                                for entry in &library.entries {
+                                    ui.selectable_value(&mut compare.texture_b, Some(entry.handle.clone()), &entry.label);
+                                }
+                            });
+                        match compare.mode {
+                            CompareMode::Split => {
+                                ui.add(egui::Slider::new(&mut compare.split, 0.0..=1.0).text("Split"));
+                            }
+                            CompareMode::OnionSkin => {
+                                ui.add(egui::Slider::new(&mut compare.blend, 0.0..=1.0).text("B opacity"));
+                            }
+                            _ => {}
+                        }
+                        // Swap A and B, going through the library so the source path follows
+                        let b_entry = library
+                            .entries
+                            .iter()
+                            .find(|entry| Some(&entry.handle) == compare.texture_b.as_ref());
+                        if let Some(b_entry) = b_entry
+                            && ui.button("Swap A/B").clicked()
+                        {
+                            compare.texture_b = texture_source.current.clone();
+                            sequence_state.clear();
+                            texture_source.current = Some(b_entry.handle.clone());
+                            texture_source.path = b_entry.path.clone();
+                            texture_source.file_path = b_entry.file_path.clone();
+                        }
+                    }
+                }
+
+                ui.separator();
+                ui.checkbox(&mut atlas_state.current.enabled, "Sprite sheet");
+                if atlas_state.current.enabled {
+                    ui.horizontal(|ui| {
+                        ui.label("Columns");
+                        ui.add(egui::DragValue::new(&mut atlas_state.current.columns).range(1..=64));
+                        ui.label("Rows");
+                        ui.add(egui::DragValue::new(&mut atlas_state.current.rows).range(1..=64));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Padding");
+                        ui.add(egui::DragValue::new(&mut atlas_state.current.padding).range(0..=256).suffix(" px"));
+                    });
+                    let last_cell = atlas_state.current.cell_count() - 1;
+                    atlas_state.current.cell = atlas_state.current.cell.min(last_cell);
+                    ui.add(egui::Slider::new(&mut atlas_state.current.cell, 0..=last_cell).text("Cell"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut atlas_state.animate, "Animate");
+                        ui.add(egui::DragValue::new(&mut atlas_state.fps).range(1.0..=60.0).suffix(" fps"));
+                    });
+                }
+
+                ui.separator();
+                ui.label("Display");
+                ui.add(
+                    egui::Slider::new(&mut tone_mapping_state.current.exposure_ev, -8.0..=8.0)
+                        .text("Exposure")
+                        .suffix(" EV")
+                        .step_by(0.1),
+                );
+                let tonemapping = &mut tone_mapping_state.current.tonemapping;
+                egui::ComboBox::from_label("Tone map")
+                    .selected_text(format!("{tonemapping:?}"))
+                    .show_ui(ui, |ui| {
+                        for option in [
+                            Tonemapping::None,
+                            Tonemapping::Reinhard,
+                            Tonemapping::ReinhardLuminance,
+                            Tonemapping::AcesFitted,
+                            Tonemapping::AgX,
+                            Tonemapping::SomewhatBoringDisplayTransform,
+                            Tonemapping::TonyMcMapface,
+                            Tonemapping::BlenderFilmic,
+                        ] {
+                            ui.selectable_value(tonemapping, option, format!("{option:?}"));
+                        }
+                    });
+                let channel_view = &mut panel_params.channel_view_state.current;
+                egui::ComboBox::from_label("Channels")
+                    .selected_text(channel_view.label())
+                    .show_ui(ui, |ui| {
+                        for option in ChannelView::ALL {
+                            ui.selectable_value(channel_view, option, option.label());
+                        }
+                    });
+
+                let visualization = &mut panel_params.visualization_state.current;
+                egui::ComboBox::from_label("Color space")
+                    .selected_text(visualization.space.label())
+                    .show_ui(ui, |ui| {
+                        for option in ColorSpace::ALL {
+                            ui.selectable_value(&mut visualization.space, option, option.label());
+                        }
+                    });
+                match visualization.space.components() {
+                    Some(components) => {
+                        let selected = match visualization.component {
+                            1..=3 => components[visualization.component as usize - 1],
+                            _ => "All",
+                        };
+                        egui::ComboBox::from_label("Component")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut visualization.component, 0, "All");
+                                for (index, name) in components.into_iter().enumerate() {
+                                    ui.selectable_value(&mut visualization.component, index as u32 + 1, name);
+                                }
+                            });
+                    }
+                    None => visualization.component = 0,
+                }
+                egui::ComboBox::from_label("Colormap")
+                    .selected_text(visualization.colormap.label())
+                    .show_ui(ui, |ui| {
+                        for option in Colormap::ALL {
+                            ui.selectable_value(&mut visualization.colormap, option, option.label());
+                        }
+                    });
+                if visualization.colormap != Colormap::None {
+                    ui.horizontal(|ui| {
+                        ui.label("Range");
+                        ui.add(egui::DragValue::new(&mut visualization.range.x).speed(0.01));
+                        ui.add(egui::DragValue::new(&mut visualization.range.y).speed(0.01));
+                    });
+                    // Depth maps rarely span 0-1, so the range can be taken from the image statistics
+                    let red_range = panel_params
+                        .histogram_state
+                        .statistics
+                        .as_ref()
+                        .map(|statistics| Vec2::new(statistics.min.x, statistics.max.x));
+                    if ui
+                        .add_enabled(red_range.is_some(), egui::Button::new("Fit to red channel"))
+                        .on_disabled_hover_text("Open the histogram to compute the image statistics")
+                        .clicked()
+                        && let Some(range) = red_range
+                    {
+                        visualization.range = range;
+                    }
+                    ui.label(egui::RichText::new("Colors the selected component, otherwise the first (red in RGB)").small());
+                }
+
+                ui.separator();
                 ui.label("Camera");
                 ui.label("Perspective projection");
 
-                if let Ok(mut projection) = camera_projection.single_mut()
+                if let Ok(mut projection) = panel_params.camera_projection.single_mut()
                     && let Projection::Perspective(perspective) = &mut *projection
                 {
                     let mut fov_degrees = perspective.fov.to_degrees();
@@ -86,18 +393,199 @@ pub fn egui_controls_ui(
                 });
 
                 ui.separator();
-                ui.label("Texture aspect ratio");
+                ui.label("Plane");
+                let texture_size = texture_source
+                    .current
+                    .as_ref()
+                    .and_then(|handle| images.get(handle))
+                    .map(|image| {
+                        let image_size = image.size().as_vec2();
+                        atlas_cell_rect(&atlas_state.current, image_size).map_or(image_size, |(_, cell_size)| cell_size)
+                    });
+                let layout = &mut panel_params.plane_layout_state.current;
                 ui.horizontal(|ui| {
-                    ui.selectable_value(&mut aspect_ratio_state.current, AspectRatio::Ratio16_9, "16:9");
-                    ui.selectable_value(&mut aspect_ratio_state.current, AspectRatio::Square, "Square");
+                    ui.checkbox(&mut layout.fit_to_grid, "Fit to grid depth");
+                    ui.add_enabled(layout.fit_to_grid, egui::Checkbox::new(&mut layout.fit_inside_grid, "Inside grid"))
+                        .on_hover_text("Keep the whole plane within the grid's width and depth");
                 });
+                let size_speed = if layout.snap_to_grid { GRID_SPACING } else { 0.05 };
+                egui::Grid::new("plane_layout_grid").num_columns(3).show(ui, |ui| {
+                    ui.label("Size");
+                    ui.add_enabled(
+                        !layout.fit_to_grid,
+                        egui::DragValue::new(&mut layout.size.x).range(PLANE_MIN_SIZE..=1000.0).speed(size_speed).suffix(" m"),
+                    );
+                    ui.add_enabled(
+                        !layout.fit_to_grid,
+                        egui::DragValue::new(&mut layout.size.y).range(PLANE_MIN_SIZE..=1000.0).speed(size_speed).suffix(" m"),
+                    );
+                    ui.end_row();
+                    ui.label("Position");
+                    ui.add(egui::DragValue::new(&mut layout.position.x).speed(0.05).prefix("x ").suffix(" m"));
+                    ui.add(egui::DragValue::new(&mut layout.position.y).speed(0.05).prefix("z ").suffix(" m"));
+                    ui.end_row();
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut layout.snap_to_grid, "Snap to grid");
+                    if ui.button("Center").clicked() {
+                        layout.position = Vec2::ZERO;
+                    }
+                });
+                if !layout.fit_to_grid
+                    && let Some(texture_size) = texture_size
+                    && ui.button("Match texture aspect").clicked()
+                {
+                    layout.size.y = layout.size.x * texture_size.y / texture_size.x;
+                }
 
                 ui.separator();
-                ui.label("Texture fit");
+                ui.label("Real-world scale");
+                let calibration = &mut panel_params.calibration;
                 ui.horizontal(|ui| {
-                    ui.selectable_value(&mut texture_mode_state.current, TextureMode::Normal, "Preserve");
-                    ui.selectable_value(&mut texture_mode_state.current, TextureMode::Stretch, "Stretch");
+                    ui.add(
+                        egui::DragValue::new(&mut calibration.pixels_per_meter)
+                            .range(0.001..=100_000.0)
+                            .speed(0.5)
+                            .suffix(" px/m"),
+                    );
+                    if ui.button("Apply").on_hover_text("Size the plane to this scale").clicked() {
+                        calibration.apply_requested = true;
+                        calibration.save_requested = true;
+                    }
+                });
+                if calibration.picking {
+                    ui.label(format!("Click point {} of 2 on the texture", calibration.points.len() + 1));
+                    if ui.button("Cancel").clicked() {
+                        calibration.picking = false;
+                        calibration.points.clear();
+                    }
+                } else if let [first, second] = calibration.points[..] {
+                    let pixel_distance = first.pixel.distance(second.pixel);
+                    ui.label(format!("Points are {pixel_distance:.1} px apart"));
+                    ui.horizontal(|ui| {
+                        ui.label("Distance");
+                        ui.add(egui::DragValue::new(&mut calibration.distance).range(0.001..=100_000.0).speed(0.01).suffix(" m"));
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button("Set scale").clicked() && pixel_distance > 0.0 {
+                            calibration.pixels_per_meter = pixel_distance / calibration.distance;
+                            calibration.apply_requested = true;
+                            calibration.save_requested = true;
+                            calibration.points.clear();
+                        }
+                        if ui.button("Clear").clicked() {
+                            calibration.points.clear();
+                        }
+                    });
+                } else if ui.button("Calibrate from two points").clicked() {
+                    calibration.picking = true;
+                    calibration.points.clear();
+                }
+
+                ui.separator();
+                ui.label("Texture aspect ratio");
+                if !panel_params.plane_layout_state.current.fit_to_grid {
+                    ui.label(egui::RichText::new("Used when the plane fits the grid depth").small());
+                }
+                ui.horizontal_wrapped(|ui| {
+                    for aspect_ratio in AspectRatio::ALL {
+                        ui.selectable_value(&mut aspect_ratio_state.current, aspect_ratio, aspect_ratio.label());
+                    }
+                });
+                if aspect_ratio_state.current == AspectRatio::Custom {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut aspect_ratio_state.custom.x).range(0.1..=100.0).speed(0.1));
+                        ui.label(":");
+                        ui.add(egui::DragValue::new(&mut aspect_ratio_state.custom.y).range(0.1..=100.0).speed(0.1));
+                    });
+                }
+
+                ui.separator();
+                ui.label("Texture fit");
+                ui.horizontal_wrapped(|ui| {
+                    for texture_mode in TextureMode::ALL {
+                        // Tiling repeats the whole texture, not the selected sprite sheet cell
+                        let available = texture_mode != TextureMode::Tile || !atlas_state.current.enabled;
+                        ui.add_enabled_ui(available, |ui| {
+                            ui.selectable_value(&mut texture_mode_state.current, texture_mode, texture_mode.label())
+                                .on_disabled_hover_text("Not available with a sprite sheet cell selected");
+                        });
+                    }
                 });
+                if texture_mode_state.current == TextureMode::Tile {
+                    ui.horizontal(|ui| {
+                        ui.label("Repeat");
+                        ui.add(egui::DragValue::new(&mut texture_mode_state.tile_repeat.x).range(0.1..=64.0).speed(0.1));
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(&mut texture_mode_state.tile_repeat.y).range(0.1..=64.0).speed(0.1));
+                    });
+                }
+                if texture_mode_state.current != TextureMode::Tile {
+                    ui.horizontal(|ui| {
+                        ui.label("Matte");
+                        ui.add_enabled_ui(!texture_mode_state.matte.transparent, |ui| {
+                            ui.color_edit_button_srgb(&mut texture_mode_state.matte.color);
+                        });
+                        ui.checkbox(&mut texture_mode_state.matte.transparent, "Transparent");
+                    });
+                }
+
+                if let Some(handle) = &texture_source.current {
+                    ui.separator();
+                    ui.label("Sampling");
+                    let format = images.get(handle).map(|image| image.texture_descriptor.format);
+                    let sampling = panel_params.sampling_state.settings_mut(handle.id());
+                    let mut edited = *sampling;
+                    ui.checkbox(&mut edited.nearest, "Pixel-exact (nearest)");
+                    ui.checkbox(&mut edited.mipmaps, "Generate mipmaps");
+                    if edited.mipmaps && format.is_some_and(|format| !supports_mipmaps(format)) {
+                        ui.label(egui::RichText::new("Not available for this format").small());
+                    }
+                    ui.add_enabled_ui(!edited.nearest, |ui| {
+                        egui::ComboBox::from_label("Anisotropy")
+                            .selected_text(format!("{}×", edited.anisotropy))
+                            .show_ui(ui, |ui| {
+                                for level in ANISOTROPY_LEVELS {
+                                    ui.selectable_value(&mut edited.anisotropy, level, format!("{level}×"));
+                                }
+                            });
+                    });
+                    if edited != *sampling {
+                        *sampling = edited;
+                        panel_params.sampling_state.last = edited;
+                    }
+                }
+
+                if let Ok(mut view) = panel_params.plane_views.single_mut() {
+                    ui.separator();
+                    ui.label("Texture view");
+                    ui.label(egui::RichText::new("Drag to pan, scroll to zoom, drag the handle to rotate").small());
+                    let mut edited = *view;
+                    let mut rotation_degrees = edited.rotation.to_degrees();
+                    egui::Grid::new("texture_view_grid").num_columns(3).show(ui, |ui| {
+                        ui.label("Pan");
+                        ui.add(egui::DragValue::new(&mut edited.pan.x).speed(0.01).suffix(" m"));
+                        ui.add(egui::DragValue::new(&mut edited.pan.y).speed(0.01).suffix(" m"));
+                        ui.end_row();
+                        ui.label("Zoom");
+                        ui.add(
+                            egui::DragValue::new(&mut edited.zoom)
+                                .range(VIEW_ZOOM_MIN..=VIEW_ZOOM_MAX)
+                                .speed(0.01)
+                                .suffix("×"),
+                        );
+                        ui.end_row();
+                        ui.label("Rotation");
+                        ui.add(egui::DragValue::new(&mut rotation_degrees).speed(0.5).suffix("°"));
+                        ui.end_row();
+                    });
+                    edited.rotation = rotation_degrees.to_radians();
+                    if ui.button("Reset view").clicked() {
+                        edited = TextureViewTransform::default();
+                    }
+                    view.set_if_neq(edited);
+                    ui.checkbox(&mut panel_params.inspector_state.enabled, "Pixel inspector on hover");
+                }
             });
         });
 
@@ -0,0 +1,162 @@
+// systems/file_browser.rs
+// Copyright (C) 2026 vecnode
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{FileBrowserEntry, FileBrowserState, ImageSequenceState, TextureSourceState, ToastState};
+use crate::systems::image_sequence::find_sequence_frames;
+use crate::systems::texture_source::{decode_image_file, is_supported_image};
+
+fn list_directory(dir: &Path) -> Result<Vec<FileBrowserEntry>, String> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|error| error.to_string())? {
+        let Ok(entry) = entry else { continue };
+        let path = entry.path();
+        let is_dir = path.is_dir();
+        if !is_dir && !is_supported_image(&path) {
+            continue;
+        }
+        entries.push(FileBrowserEntry {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            path,
+            is_dir,
+        });
+    }
+
+    // Directories first, then files, each alphabetically
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    Ok(entries)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn file_browser_window(
+    mut contexts: EguiContexts,
+    mut browser_state: ResMut<FileBrowserState>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut toast_state: ResMut<ToastState>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+) {
+    if !browser_state.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    if browser_state.listed_dir.as_ref() != Some(&browser_state.current_dir) {
+        let current_dir = browser_state.current_dir.clone();
+        match list_directory(&current_dir) {
+            Ok(entries) => {
+                browser_state.entries = entries;
+                browser_state.error = None;
+            }
+            Err(error) => {
+                browser_state.entries.clear();
+                browser_state.error = Some(error);
+            }
+        }
+        browser_state.listed_dir = Some(current_dir);
+    }
+
+    let mut open = browser_state.open;
+    let mut next_dir: Option<PathBuf> = None;
+    let mut chosen_file: Option<PathBuf> = None;
+    let mut load_asset_path = false;
+
+    egui::Window::new("Open image")
+        .open(&mut open)
+        .default_size([360.0, 420.0])
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("⬆").on_hover_text("Parent directory").clicked()
+                    && let Some(parent) = browser_state.current_dir.parent()
+                {
+                    next_dir = Some(parent.to_path_buf());
+                }
+                if ui.button("⟳").on_hover_text("Refresh").clicked() {
+                    browser_state.listed_dir = None;
+                }
+                ui.label(browser_state.current_dir.display().to_string());
+            });
+            ui.separator();
+
+            if let Some(error) = &browser_state.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for entry in &browser_state.entries {
+                        let label = if entry.is_dir {
+                            format!("📁 {}", entry.name)
+                        } else {
+                            format!("🖼 {}", entry.name)
+                        };
+                        if ui.selectable_label(false, label).clicked() {
+                            if entry.is_dir {
+                                next_dir = Some(entry.path.clone());
+                            } else {
+                                chosen_file = Some(entry.path.clone());
+                            }
+                        }
+                    }
+                });
+
+            ui.checkbox(&mut browser_state.open_as_sequence, "Open as numbered image sequence");
+
+            // Directory listing isn't available in the browser, so assets can also be opened by path
+            ui.separator();
+            ui.label("Asset path");
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut browser_state.asset_path);
+                if ui.button("Load").clicked() && !browser_state.asset_path.trim().is_empty() {
+                    load_asset_path = true;
+                }
+            });
+        });
+
+    browser_state.open = open;
+    if let Some(dir) = next_dir {
+        browser_state.current_dir = dir;
+    }
+
+    if let Some(path) = chosen_file {
+        if browser_state.open_as_sequence {
+            let frames = find_sequence_frames(&path);
+            info!("Opened image sequence with {} frames", frames.len());
+            sequence_state.load(frames);
+            browser_state.open = false;
+        } else {
+            match decode_image_file(&path) {
+                Ok(image) => {
+                    sequence_state.clear();
+                    texture_source.set_file(&path, images.add(image));
+                    browser_state.open = false;
+                }
+                Err(error) => {
+                    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                    toast_state.show_error(format!("Could not decode {file_name}: {error}"));
+                }
+            }
+        }
+    }
+
+    if load_asset_path {
+        let asset_path = browser_state.asset_path.trim().to_string();
+        let handle = asset_server.load(asset_path.clone());
+        texture_source.set_asset(asset_path, handle);
+        sequence_state.clear();
+        browser_state.open = false;
+    }
+}
@@ -0,0 +1,160 @@
+// systems/filters.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::components::{ConvolutionFilter, CustomKernel};
+use crate::constants::MAX_KERNEL_SIZE;
+use crate::systems::pixels::PixelBuffer;
+
+const SOBEL_X: [f32; 9] = [-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0];
+const SOBEL_Y: [f32; 9] = [-1.0, -2.0, -1.0, 0.0, 0.0, 0.0, 1.0, 2.0, 1.0];
+const LAPLACIAN: [f32; 9] = [0.0, 1.0, 0.0, 1.0, -4.0, 1.0, 0.0, 1.0, 0.0];
+const EMBOSS: [f32; 9] = [-2.0, -1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 1.0, 2.0];
+
+// Convolves the color channels with a square kernel, clamping reads at the image edges. Alpha
+// is left as is.
+fn convolve(buffer: &PixelBuffer, kernel: &[f32], size: usize) -> Vec<Vec3> {
+    let radius = (size / 2) as i32;
+    let mut output = Vec::with_capacity(buffer.pixels.len());
+    for y in 0..buffer.size.y as i32 {
+        for x in 0..buffer.size.x as i32 {
+            let mut sum = Vec3::ZERO;
+            for (index, &weight) in kernel.iter().enumerate() {
+                if weight != 0.0 {
+                    let offset_x = (index % size) as i32 - radius;
+                    let offset_y = (index / size) as i32 - radius;
+                    sum += buffer.get_clamped(x + offset_x, y + offset_y).truncate() * weight;
+                }
+            }
+            output.push(sum);
+        }
+    }
+    output
+}
+
+// Blurs with a 1D kernel applied horizontally then vertically, which is much cheaper than the
+// equivalent 2D kernel for large radii
+fn convolve_separable(buffer: &mut PixelBuffer, kernel: &[f32]) {
+    let radius = (kernel.len() / 2) as i32;
+    for (step_x, step_y) in [(1, 0), (0, 1)] {
+        let mut output = Vec::with_capacity(buffer.pixels.len());
+        for y in 0..buffer.size.y as i32 {
+            for x in 0..buffer.size.x as i32 {
+                let mut sum = Vec3::ZERO;
+                for (index, &weight) in kernel.iter().enumerate() {
+                    let offset = index as i32 - radius;
+                    sum += buffer.get_clamped(x + offset * step_x, y + offset * step_y).truncate() * weight;
+                }
+                output.push(sum);
+            }
+        }
+        write_colors(buffer, output);
+    }
+}
+
+fn write_colors(buffer: &mut PixelBuffer, colors: Vec<Vec3>) {
+    for (pixel, color) in buffer.pixels.iter_mut().zip(colors) {
+        *pixel = color.extend(pixel.w);
+    }
+}
+
+fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    let radius = (sigma * 3.0).ceil().max(1.0) as i32;
+    let weights: Vec<f32> = (-radius..=radius)
+        .map(|offset| (-(offset * offset) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = weights.iter().sum();
+    weights.into_iter().map(|weight| weight / total).collect()
+}
+
+pub fn apply_filter(buffer: &mut PixelBuffer, filter: &ConvolutionFilter) {
+    match filter {
+        ConvolutionFilter::BoxBlur { radius } => {
+            let width = (radius * 2 + 1) as usize;
+            convolve_separable(buffer, &vec![1.0 / width as f32; width]);
+        }
+        ConvolutionFilter::GaussianBlur { sigma } => {
+            convolve_separable(buffer, &gaussian_kernel(*sigma));
+        }
+        ConvolutionFilter::Sharpen { amount } => {
+            let kernel = [0.0, -amount, 0.0, -amount, 1.0 + 4.0 * amount, -amount, 0.0, -amount, 0.0];
+            let colors = convolve(buffer, &kernel, 3);
+            write_colors(buffer, colors);
+        }
+        ConvolutionFilter::Sobel => {
+            let gradient_x = convolve(buffer, &SOBEL_X, 3);
+            let gradient_y = convolve(buffer, &SOBEL_Y, 3);
+            let magnitude = gradient_x
+                .into_iter()
+                .zip(gradient_y)
+                .map(|(gx, gy)| (gx * gx + gy * gy).powf(0.5))
+                .collect();
+            write_colors(buffer, magnitude);
+        }
+        ConvolutionFilter::Laplacian => {
+            let colors = convolve(buffer, &LAPLACIAN, 3).into_iter().map(Vec3::abs).collect();
+            write_colors(buffer, colors);
+        }
+        ConvolutionFilter::Emboss { strength } => {
+            // Relief on top of the original, so flat areas keep their color
+            let mut kernel = EMBOSS.map(|weight| weight * strength);
+            kernel[4] = 1.0;
+            let colors = convolve(buffer, &kernel, 3);
+            write_colors(buffer, colors);
+        }
+        ConvolutionFilter::Custom(kernel) => {
+            let total: f32 = kernel.weights.iter().sum();
+            let scale = if kernel.normalize && total.abs() > f32::EPSILON { 1.0 / total } else { 1.0 };
+            let colors = convolve(buffer, &kernel.weights, kernel.size)
+                .into_iter()
+                .map(|color| color * scale + Vec3::splat(kernel.bias))
+                .collect();
+            write_colors(buffer, colors);
+        }
+    }
+}
+
+fn kernel_editor(ui: &mut egui::Ui, kernel: &mut CustomKernel) {
+    let mut size = kernel.size;
+    egui::ComboBox::from_label("Size")
+        .selected_text(format!("{size} × {size}"))
+        .show_ui(ui, |ui| {
+            for option in (3..=MAX_KERNEL_SIZE).step_by(2) {
+                ui.selectable_value(&mut size, option, format!("{option} × {option}"));
+            }
+        });
+    if size != kernel.size {
+        kernel.resize(size);
+    }
+
+    egui::Grid::new("kernel_weights").spacing([2.0, 2.0]).show(ui, |ui| {
+        for y in 0..kernel.size {
+            for x in 0..kernel.size {
+                ui.add(egui::DragValue::new(kernel.weight_mut(x, y)).speed(0.05).max_decimals(3));
+            }
+            ui.end_row();
+        }
+    });
+    ui.checkbox(&mut kernel.normalize, "Normalize by weight sum");
+    ui.add(egui::Slider::new(&mut kernel.bias, -1.0..=1.0).text("Bias"));
+}
+
+pub fn filter_controls(ui: &mut egui::Ui, filter: &mut ConvolutionFilter) {
+    match filter {
+        ConvolutionFilter::BoxBlur { radius } => {
+            ui.add(egui::Slider::new(radius, 1..=32).suffix(" px").text("Radius"));
+        }
+        ConvolutionFilter::GaussianBlur { sigma } => {
+            ui.add(egui::Slider::new(sigma, 0.3..=16.0).suffix(" px").text("Sigma"));
+        }
+        ConvolutionFilter::Sharpen { amount } => {
+            ui.add(egui::Slider::new(amount, 0.0..=4.0).text("Amount"));
+        }
+        ConvolutionFilter::Sobel | ConvolutionFilter::Laplacian => {}
+        ConvolutionFilter::Emboss { strength } => {
+            ui.add(egui::Slider::new(strength, 0.0..=4.0).text("Strength"));
+        }
+        ConvolutionFilter::Custom(kernel) => kernel_editor(ui, kernel),
+    }
+}
@@ -0,0 +1,370 @@
+// systems/generators.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{GeneratorKind, GeneratorParams, GeneratorState, ImageSequenceState, TextureLibrary, TextureSourceState};
+
+// 5x7 bitmap glyphs, one byte per row with the leftmost pixel in bit 4
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+fn glyph(character: char) -> [u8; 7] {
+    match character.to_ascii_uppercase() {
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        ' ' => [0x00; 7],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+        '\'' => [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        _ => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // '?'
+    }
+}
+
+// Classic Perlin gradient noise with a seeded permutation table
+struct PerlinNoise {
+    permutation: [u8; 512],
+}
+
+impl PerlinNoise {
+    fn new(seed: u32) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        // xorshift32 drives a Fisher-Yates shuffle; zero would get stuck, so nudge it
+        let mut state = seed.wrapping_mul(0x9E37_79B9) | 1;
+        for i in (1..256).rev() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            table.swap(i, state as usize % (i + 1));
+        }
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+        }
+    }
+
+    fn gradient(hash: u8, x: f32, y: f32) -> f32 {
+        match hash & 7 {
+            0 => x + y,
+            1 => -x + y,
+            2 => x - y,
+            3 => -x - y,
+            4 => x,
+            5 => -x,
+            6 => y,
+            _ => -y,
+        }
+    }
+
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let xi = (x0 as i32 & 255) as usize;
+        let yi = (y0 as i32 & 255) as usize;
+        let p = &self.permutation;
+        let hash = |dx: usize, dy: usize| p[p[xi + dx] as usize + yi + dy];
+
+        let (u, v) = (fade(fx), fade(fy));
+        let bottom = Self::gradient(hash(0, 0), fx, fy)
+            .lerp(Self::gradient(hash(1, 0), fx - 1.0, fy), u);
+        let top = Self::gradient(hash(0, 1), fx, fy - 1.0)
+            .lerp(Self::gradient(hash(1, 1), fx - 1.0, fy - 1.0), u);
+        bottom.lerp(top, v)
+    }
+
+    // Fractal sum of octaves, remapped to roughly [0, 1]
+    fn fbm(&self, x: f32, y: f32, octaves: u32) -> f32 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut normalization = 0.0;
+        for _ in 0..octaves.max(1) {
+            total += self.sample(x * frequency, y * frequency) * amplitude;
+            normalization += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        (total / normalization * 0.5 + 0.5).clamp(0.0, 1.0)
+    }
+}
+
+fn mix(a: [u8; 4], b: [u8; 4], t: f32) -> [u8; 4] {
+    std::array::from_fn(|i| (a[i] as f32).lerp(b[i] as f32, t).round() as u8)
+}
+
+pub fn generate_texture(params: &GeneratorParams) -> Image {
+    let width = params.width.max(1);
+    let height = params.height.max(1);
+    let mut data = vec![0u8; (width * height * 4) as usize];
+    let mut put = |x: u32, y: u32, color: [u8; 4]| {
+        let index = ((y * width + x) * 4) as usize;
+        data[index..index + 4].copy_from_slice(&color);
+    };
+
+    match params.kind {
+        GeneratorKind::Checkerboard => {
+            let cell = params.checker_size.max(1);
+            for y in 0..height {
+                for x in 0..width {
+                    let even = (x / cell + y / cell).is_multiple_of(2);
+                    put(x, y, if even { params.color_a } else { params.color_b });
+                }
+            }
+        }
+        GeneratorKind::UvGradient => {
+            // Red follows U, green follows V, with a line every eighth to show scale and offset
+            for y in 0..height {
+                for x in 0..width {
+                    let u = (x as f32 + 0.5) / width as f32;
+                    let v = (y as f32 + 0.5) / height as f32;
+                    let on_line = x.is_multiple_of((width / 8).max(1)) || y.is_multiple_of((height / 8).max(1));
+                    let color = if on_line {
+                        [255, 255, 255, 255]
+                    } else {
+                        [(u * 255.0) as u8, (v * 255.0) as u8, 64, 255]
+                    };
+                    put(x, y, color);
+                }
+            }
+        }
+        GeneratorKind::Noise => {
+            let noise = PerlinNoise::new(params.seed);
+            for y in 0..height {
+                for x in 0..width {
+                    let value = noise.fbm(x as f32 / params.noise_scale, y as f32 / params.noise_scale, params.octaves);
+                    put(x, y, mix(params.color_a, params.color_b, value));
+                }
+            }
+        }
+        GeneratorKind::SolidColor => {
+            for y in 0..height {
+                for x in 0..width {
+                    put(x, y, params.color_a);
+                }
+            }
+        }
+        GeneratorKind::TextLabel => {
+            for y in 0..height {
+                for x in 0..width {
+                    put(x, y, params.color_b);
+                }
+            }
+
+            // Center each line, scaling every glyph pixel to a square block
+            let scale = params.text_scale.max(1);
+            let lines: Vec<&str> = params.text.lines().collect();
+            let line_height = (GLYPH_HEIGHT + 2) * scale;
+            let block_top = (height as i64 - (lines.len() as u32 * line_height) as i64) / 2;
+            for (line_index, line) in lines.iter().enumerate() {
+                let line_width = line.chars().count() as u32 * (GLYPH_WIDTH + 1) * scale;
+                let left = (width as i64 - line_width as i64) / 2;
+                let top = block_top + (line_index as u32 * line_height) as i64 + scale as i64;
+                for (char_index, character) in line.chars().enumerate() {
+                    let rows = glyph(character);
+                    let glyph_left = left + (char_index as u32 * (GLYPH_WIDTH + 1) * scale) as i64;
+                    for (row, bits) in rows.iter().enumerate() {
+                        for column in 0..GLYPH_WIDTH {
+                            if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                                continue;
+                            }
+                            for sy in 0..scale {
+                                for sx in 0..scale {
+                                    let px = glyph_left + (column * scale + sx) as i64;
+                                    let py = top + (row as u32 * scale + sy) as i64;
+                                    if (0..width as i64).contains(&px) && (0..height as i64).contains(&py) {
+                                        put(px as u32, py as u32, params.color_a);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+pub fn apply_texture_generator(
+    mut generator_state: ResMut<GeneratorState>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+    mut library: ResMut<TextureLibrary>,
+) {
+    let generate_requested = generator_state.generate_requested;
+    let showing_output = generator_state.handle.is_some() && texture_source.current == generator_state.handle;
+    let params_changed = generator_state.previous.as_ref() != Some(&generator_state.current);
+
+    // Parameters update live while the generated texture is on the plane, once a drag is released
+    let live_update = showing_output && params_changed && !generator_state.dragging;
+    if !generate_requested && !live_update {
+        return;
+    }
+    generator_state.generate_requested = false;
+    generator_state.previous = Some(generator_state.current.clone());
+
+    let image = generate_texture(&generator_state.current);
+    let label = format!("Generated {}", generator_state.current.kind.label());
+    match generator_state.handle.clone() {
+        // Live updates reuse the handle, which emits AssetEvent::Modified and re-runs the fit for
+        // the new size. Its library entry follows the new parameters.
+        Some(handle) if !generate_requested && images.insert(handle.id(), image.clone()).is_ok() => {
+            if let Some(entry) = library.entries.iter_mut().find(|entry| entry.handle == handle) {
+                entry.label = label.clone();
+                entry.path = label.clone();
+            }
+        }
+        // Generate always adds a new texture, so earlier results stay in the library
+        _ => {
+            let handle = images.add(image);
+            generator_state.handle = Some(handle.clone());
+            sequence_state.clear();
+            texture_source.current = Some(handle);
+            texture_source.file_path = None;
+        }
+    }
+    texture_source.path = label;
+}
+
+pub fn texture_generator_window(mut contexts: EguiContexts, mut generator_state: ResMut<GeneratorState>) {
+    if !generator_state.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = generator_state.open;
+    egui::Window::new("Generate texture")
+        .open(&mut open)
+        .default_width(260.0)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let params = &mut generator_state.current;
+            egui::ComboBox::from_label("Pattern")
+                .selected_text(params.kind.label())
+                .show_ui(ui, |ui| {
+                    for kind in GeneratorKind::ALL {
+                        ui.selectable_value(&mut params.kind, kind, kind.label());
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                ui.label("Size");
+                ui.add(egui::DragValue::new(&mut params.width).range(1..=4096).suffix(" px"));
+                ui.label("×");
+                ui.add(egui::DragValue::new(&mut params.height).range(1..=4096).suffix(" px"));
+            });
+
+            match params.kind {
+                GeneratorKind::Checkerboard => {
+                    ui.add(egui::Slider::new(&mut params.checker_size, 1..=256).text("Cell px"));
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_a);
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_b);
+                        ui.label("Colors");
+                    });
+                }
+                GeneratorKind::UvGradient => {
+                    ui.label("Red = U, green = V, white lines every 1/8");
+                }
+                GeneratorKind::Noise => {
+                    ui.add(egui::Slider::new(&mut params.noise_scale, 2.0..=512.0).text("Scale px"));
+                    ui.add(egui::Slider::new(&mut params.octaves, 1..=8).text("Octaves"));
+                    ui.horizontal(|ui| {
+                        ui.label("Seed");
+                        ui.add(egui::DragValue::new(&mut params.seed));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_a);
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_b);
+                        ui.label("Low / high");
+                    });
+                }
+                GeneratorKind::SolidColor => {
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_a);
+                        ui.label("Color");
+                    });
+                }
+                GeneratorKind::TextLabel => {
+                    ui.text_edit_multiline(&mut params.text);
+                    ui.add(egui::Slider::new(&mut params.text_scale, 1..=32).text("Pixel scale"));
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_a);
+                        ui.color_edit_button_srgba_unmultiplied(&mut params.color_b);
+                        ui.label("Text / background");
+                    });
+                }
+            }
+
+            ui.separator();
+            if ui.button("Apply to plane").clicked() {
+                generator_state.generate_requested = true;
+            }
+        });
+    generator_state.open = open;
+    let dragging = open && ctx.is_using_pointer();
+    if generator_state.dragging != dragging {
+        generator_state.dragging = dragging;
+    }
+}
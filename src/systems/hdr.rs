@@ -0,0 +1,165 @@
+// systems/hdr.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, WgpuFeatures};
+use bevy::render::renderer::RenderDevice;
+use crate::components::{RightCamera, TextureSourceState, TexturedPlane, ToneMappingState};
+use crate::materials::TexturePlaneMaterial;
+
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// Converts formats the unlit material can't sample (16-bit integer/normalized data) or can't
+// filter on this device (32-bit float) into a filterable float format. 16-bit color is
+// sRGB-encoded like its 8-bit counterpart, so it's decoded to linear on the way. 16-bit grayscale
+// is usually data such as depth or height, so it's only normalized and the analysis tools see
+// the stored values.
+pub fn to_display_format(image: &Image, float32_filterable: bool) -> Option<Image> {
+    let data = image.data.as_ref()?;
+    let unorm16 = |bytes: &[u8]| u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / u16::MAX as f32;
+
+    let texels: Vec<[f32; 4]> = match image.texture_descriptor.format {
+        TextureFormat::R16Uint => data
+            .chunks_exact(2)
+            .map(|texel| {
+                let gray = unorm16(texel);
+                [gray, gray, gray, 1.0]
+            })
+            .collect(),
+        TextureFormat::Rg16Uint => data
+            .chunks_exact(4)
+            .map(|texel| {
+                let gray = unorm16(&texel[0..2]);
+                [gray, gray, gray, unorm16(&texel[2..4])]
+            })
+            .collect(),
+        TextureFormat::Rgba16Unorm => data
+            .chunks_exact(8)
+            .map(|texel| {
+                [
+                    srgb_to_linear(unorm16(&texel[0..2])),
+                    srgb_to_linear(unorm16(&texel[2..4])),
+                    srgb_to_linear(unorm16(&texel[4..6])),
+                    unorm16(&texel[6..8]),
+                ]
+            })
+            .collect(),
+        TextureFormat::Rgba32Float if !float32_filterable => data
+            .chunks_exact(16)
+            .map(|texel| {
+                std::array::from_fn(|channel| {
+                    let offset = channel * 4;
+                    f32::from_le_bytes([texel[offset], texel[offset + 1], texel[offset + 2], texel[offset + 3]])
+                })
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    let (format, bytes): (TextureFormat, Vec<u8>) = if float32_filterable {
+        (
+            TextureFormat::Rgba32Float,
+            texels.iter().flatten().flat_map(|value| value.to_le_bytes()).collect(),
+        )
+    } else {
+        (
+            TextureFormat::Rgba16Float,
+            texels
+                .iter()
+                .flatten()
+                .flat_map(|value| half::f16::from_f32(*value).to_le_bytes())
+                .collect(),
+        )
+    };
+
+    let mut converted = Image::new(
+        Extent3d {
+            width: image.width(),
+            height: image.height(),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        bytes,
+        format,
+        image.asset_usage,
+    );
+    converted.sampler = image.sampler.clone();
+    Some(converted)
+}
+
+// Runs when the source changes or its image is added, loaded or modified, so the conversion
+// lands before the image is first bound to the material
+pub fn prepare_display_texture(
+    texture_source: Res<TextureSourceState>,
+    mut images: ResMut<Assets<Image>>,
+    mut image_events: MessageReader<AssetEvent<Image>>,
+    mut checked_source: Local<Option<AssetId<Image>>>,
+    render_device: Option<Res<RenderDevice>>,
+) {
+    let Some(handle) = &texture_source.current else {
+        return;
+    };
+    let id = handle.id();
+    let mut image_changed = false;
+    for event in image_events.read() {
+        if event.is_added(id) || event.is_loaded_with_dependencies(id) || event.is_modified(id) {
+            image_changed = true;
+        }
+    }
+    if !image_changed && *checked_source == Some(id) {
+        return;
+    }
+    let Some(image) = images.get(id) else {
+        return;
+    };
+    *checked_source = Some(id);
+
+    let float32_filterable = render_device
+        .is_some_and(|device| device.features().contains(WgpuFeatures::FLOAT32_FILTERABLE));
+    if let Some(converted) = to_display_format(image, float32_filterable) {
+        info!(
+            "Converted {:?} texture to {:?} for display",
+            image.texture_descriptor.format, converted.texture_descriptor.format
+        );
+        let _ = images.insert(handle.id(), converted);
+    }
+}
+
+pub fn apply_tone_mapping(
+    mut tone_mapping_state: ResMut<ToneMappingState>,
+    mut materials: ResMut<Assets<TexturePlaneMaterial>>,
+    plane_query: Query<&MeshMaterial3d<TexturePlaneMaterial>, With<TexturedPlane>>,
+    mut camera_query: Query<&mut Tonemapping, With<RightCamera>>,
+) {
+    if tone_mapping_state.current == tone_mapping_state.previous {
+        return;
+    }
+    tone_mapping_state.previous = tone_mapping_state.current;
+
+    // The plane is unlit, so exposure is applied as a linear multiplier on the texture
+    let exposure_scale = 2.0_f32.powf(tone_mapping_state.current.exposure_ev);
+    for material_3d in plane_query.iter() {
+        if let Some(material) = materials.get_mut(&material_3d.0) {
+            material.base.base_color = Color::linear_rgb(exposure_scale, exposure_scale, exposure_scale);
+            material.extension.exposure = exposure_scale;
+        }
+    }
+    for mut tonemapping in camera_query.iter_mut() {
+        *tonemapping = tone_mapping_state.current.tonemapping;
+    }
+}
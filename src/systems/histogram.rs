@@ -0,0 +1,182 @@
+// systems/histogram.rs
+// Copyright (C) 2026 vecnode
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{HistogramState, ImageStatistics, TextureSourceState};
+use crate::constants::HISTOGRAM_BINS;
+use crate::systems::pixels::PixelBuffer;
+
+const LUMA: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+const HISTOGRAM_COLORS: [egui::Color32; 4] = [
+    egui::Color32::from_rgb(230, 70, 70),
+    egui::Color32::from_rgb(70, 200, 70),
+    egui::Color32::from_rgb(80, 130, 255),
+    egui::Color32::from_gray(220),
+];
+const HISTOGRAM_LABELS: [&str; 4] = ["R", "G", "B", "Luma"];
+
+fn compute_statistics(buffer: &PixelBuffer) -> ImageStatistics {
+    let mut histograms = [[0; HISTOGRAM_BINS]; 4];
+    let mut min = Vec4::splat(f32::INFINITY);
+    let mut max = Vec4::splat(f32::NEG_INFINITY);
+    // Accumulated in f64 so large images don't lose precision
+    let mut sum = [0.0_f64; 4];
+    let mut sum_squares = [0.0_f64; 4];
+    let mut unique = HashSet::new();
+
+    // Values outside [0, 1] (HDR) land in the end bins
+    let bin = |value: f32| ((value.clamp(0.0, 1.0) * (HISTOGRAM_BINS - 1) as f32).round()) as usize;
+    for pixel in &buffer.pixels {
+        min = min.min(*pixel);
+        max = max.max(*pixel);
+        for (channel, value) in pixel.to_array().into_iter().enumerate() {
+            sum[channel] += value as f64;
+            sum_squares[channel] += (value as f64).powi(2);
+        }
+        histograms[0][bin(pixel.x)] += 1;
+        histograms[1][bin(pixel.y)] += 1;
+        histograms[2][bin(pixel.z)] += 1;
+        histograms[3][bin(pixel.truncate().dot(LUMA))] += 1;
+        unique.insert(pixel.to_array().map(f32::to_bits));
+    }
+
+    let count = buffer.pixels.len().max(1) as f64;
+    let mean = sum.map(|sum| sum / count);
+    let std_dev = [0, 1, 2, 3].map(|channel| (sum_squares[channel] / count - mean[channel].powi(2)).max(0.0).sqrt());
+    ImageStatistics {
+        size: buffer.size,
+        histograms,
+        min,
+        max,
+        mean: Vec4::from_array(mean.map(|mean| mean as f32)),
+        std_dev: Vec4::from_array(std_dev.map(|std_dev| std_dev as f32)),
+        unique_colors: unique.len(),
+    }
+}
+
+// Recomputes the statistics of the texture shown on the plane while the window is open
+pub fn update_image_statistics(
+    mut histogram_state: ResMut<HistogramState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    mut image_events: MessageReader<AssetEvent<Image>>,
+) {
+    let displayed_id = texture_source.displayed().map(Handle::id);
+    let mut modified = false;
+    for event in image_events.read() {
+        if let Some(id) = displayed_id
+            && event.is_modified(id)
+        {
+            modified = true;
+        }
+    }
+    if !histogram_state.open {
+        // Changes aren't tracked while closed, so recompute on the next open
+        if histogram_state.computed_for.is_some() {
+            histogram_state.computed_for = None;
+        }
+        return;
+    }
+    if !modified && histogram_state.computed_for == displayed_id {
+        return;
+    }
+
+    // Wait for the texture to load
+    let Some(image) = displayed_id.and_then(|id| images.get(id)) else {
+        return;
+    };
+    histogram_state.computed_for = displayed_id;
+    histogram_state.statistics = PixelBuffer::from_image(image).map(|buffer| compute_statistics(&buffer));
+}
+
+fn histogram_plot(ui: &mut egui::Ui, statistics: &ImageStatistics, visible: [bool; 4], log_scale: bool) {
+    let (response, painter) = ui.allocate_painter(egui::vec2(ui.available_width(), 140.0), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+
+    let scale = |count: u32| if log_scale { (count as f32 + 1.0).ln() } else { count as f32 };
+    let peak = (0..4)
+        .filter(|&channel| visible[channel])
+        .flat_map(|channel| statistics.histograms[channel].iter().copied())
+        .map(scale)
+        .fold(0.0_f32, f32::max)
+        .max(f32::EPSILON);
+
+    for channel in (0..4).filter(|&channel| visible[channel]) {
+        let points: Vec<egui::Pos2> = statistics.histograms[channel]
+            .iter()
+            .enumerate()
+            .map(|(index, &count)| {
+                egui::pos2(
+                    egui::lerp(rect.left()..=rect.right(), index as f32 / (HISTOGRAM_BINS - 1) as f32),
+                    egui::lerp(rect.bottom()..=rect.top(), scale(count) / peak),
+                )
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, HISTOGRAM_COLORS[channel])));
+    }
+
+    if let Some(pointer) = response.hover_pos() {
+        let index = (((pointer.x - rect.left()) / rect.width()) * (HISTOGRAM_BINS - 1) as f32).round() as usize;
+        let index = index.min(HISTOGRAM_BINS - 1);
+        let counts: Vec<String> = (0..4)
+            .filter(|&channel| visible[channel])
+            .map(|channel| format!("{} {}", HISTOGRAM_LABELS[channel], statistics.histograms[channel][index]))
+            .collect();
+        response.on_hover_text(format!("Bin {index}\n{}", counts.join("\n")));
+    }
+}
+
+pub fn histogram_window(mut contexts: EguiContexts, mut histogram_state: ResMut<HistogramState>) {
+    if !histogram_state.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = histogram_state.open;
+    egui::Window::new("Histogram")
+        .open(&mut open)
+        .default_width(340.0)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let state = &mut *histogram_state;
+            let Some(statistics) = &state.statistics else {
+                ui.label("No statistics for this texture format");
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                for (channel, label) in HISTOGRAM_LABELS.iter().enumerate() {
+                    ui.checkbox(&mut state.visible[channel], *label);
+                }
+                ui.checkbox(&mut state.log_scale, "Log");
+            });
+            histogram_plot(ui, statistics, state.visible, state.log_scale);
+
+            ui.separator();
+            egui::Grid::new("channel_statistics").striped(true).show(ui, |ui| {
+                for heading in ["", "Min", "Max", "Mean", "Std dev"] {
+                    ui.strong(heading);
+                }
+                ui.end_row();
+                for (channel, label) in ["R", "G", "B", "A"].iter().enumerate() {
+                    ui.label(*label);
+                    for value in [statistics.min, statistics.max, statistics.mean, statistics.std_dev] {
+                        ui.monospace(format!("{:.4}", value[channel]));
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.label(format!(
+                "{} × {} px, {} unique colors",
+                statistics.size.x, statistics.size.y, statistics.unique_colors
+            ));
+            ui.label(egui::RichText::new("Values are in the texture's stored encoding, with 8-bit channels scaled to 0–1").small());
+        });
+    histogram_state.open = open;
+}
@@ -0,0 +1,52 @@
+// systems/hot_reload.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use crate::components::{HotReloadState, TextureSourceState, ToastState};
+use crate::systems::texture_source::decode_image_file;
+
+pub fn hot_reload_texture(
+    time: Res<Time>,
+    mut hot_reload_state: ResMut<HotReloadState>,
+    texture_source: Res<TextureSourceState>,
+    mut images: ResMut<Assets<Image>>,
+    mut toast_state: ResMut<ToastState>,
+) {
+    // Start watching whenever the current texture points at a different file
+    if hot_reload_state.watched_path != texture_source.file_path {
+        hot_reload_state.watched_path = texture_source.file_path.clone();
+        hot_reload_state.last_modified = hot_reload_state
+            .watched_path
+            .as_ref()
+            .and_then(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok());
+    }
+
+    if !hot_reload_state.enabled || !hot_reload_state.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let (Some(path), Some(handle)) = (hot_reload_state.watched_path.clone(), &texture_source.current) else {
+        return;
+    };
+    let Ok(modified) = std::fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+        return;
+    };
+    if hot_reload_state.last_modified == Some(modified) {
+        return;
+    }
+    hot_reload_state.last_modified = Some(modified);
+
+    // Replacing the asset in place keeps every handle valid and emits AssetEvent::Modified,
+    // which makes update_texture_aspect_ratio re-run the fit for the new dimensions.
+    // A half-written file fails to decode; the next write bumps the timestamp and retries.
+    match decode_image_file(&path) {
+        Ok(image) => {
+            if images.insert(handle.id(), image).is_ok() {
+                info!("Reloaded texture {}", path.display());
+            }
+        }
+        Err(error) => {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            toast_state.show_error(format!("Could not reload {file_name}: {error}"));
+        }
+    }
+}
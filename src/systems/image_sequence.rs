@@ -0,0 +1,199 @@
+// systems/image_sequence.rs
+// Copyright (C) 2026 vecnode
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::image::{ImageSampler, ImageSamplerDescriptor};
+use bevy::render::render_resource::WgpuFeatures;
+use bevy::render::renderer::RenderDevice;
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool};
+use crate::components::{ImageSequenceState, TextureModeState, TextureSamplingState, TextureSourceState, ToastState};
+use crate::constants::{SEQUENCE_KEEP_BEHIND_FRAMES, SEQUENCE_MAX_DECODES, SEQUENCE_PREFETCH_FRAMES};
+use crate::systems::hdr::to_display_format;
+use crate::systems::sampling::sampler_descriptor;
+use crate::systems::texture_source::decode_image_file;
+
+// Splits "frame_0001.png" into ("frame_", 1); None when the stem has no trailing number.
+fn split_frame_number(path: &Path) -> Option<(String, u64)> {
+    let stem = path.file_stem()?.to_str()?;
+    let prefix = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = stem[prefix.len()..].parse().ok()?;
+    Some((prefix.to_string(), number))
+}
+
+pub fn find_sequence_frames(path: &Path) -> Vec<PathBuf> {
+    let (Some((prefix, _)), Some(dir)) = (split_frame_number(path), path.parent()) else {
+        return vec![path.to_path_buf()];
+    };
+    let extension = path.extension();
+
+    let mut frames: Vec<(u64, PathBuf)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|candidate| candidate.extension() == extension)
+        .filter_map(|candidate| {
+            let (candidate_prefix, number) = split_frame_number(&candidate)?;
+            (candidate_prefix == prefix).then_some((number, candidate))
+        })
+        .collect();
+    frames.sort_by_key(|(number, _)| *number);
+
+    if frames.is_empty() {
+        vec![path.to_path_buf()]
+    } else {
+        frames.into_iter().map(|(_, frame)| frame).collect()
+    }
+}
+
+// Decodes a frame on a worker thread, ready for display: converted to a filterable format and
+// with the current sampler. The CPU copy is kept for the inspector, histogram and adjustments.
+fn decode_frame(path: PathBuf, float32_filterable: bool, sampler: ImageSamplerDescriptor) -> Result<Image, String> {
+    let image = decode_image_file(&path)?;
+    let mut image = to_display_format(&image, float32_filterable).unwrap_or(image);
+    image.sampler = ImageSampler::Descriptor(sampler);
+    Ok(image)
+}
+
+// Frames that should be decoded: the playhead, a few ahead of it (wrapping when looping) and a
+// couple behind it
+fn cache_window(current: usize, frame_count: usize, looping: bool) -> Vec<usize> {
+    let ahead = (1..=SEQUENCE_PREFETCH_FRAMES)
+        .map(|offset| current + offset)
+        .filter_map(|index| if index < frame_count { Some(index) } else { looping.then_some(index % frame_count) });
+    let behind = (1..=SEQUENCE_KEEP_BEHIND_FRAMES.min(current)).map(|offset| current - offset);
+
+    let mut window = vec![current];
+    for index in ahead.chain(behind) {
+        if !window.contains(&index) {
+            window.push(index);
+        }
+    }
+    window
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn advance_image_sequence(
+    time: Res<Time>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut toast_state: ResMut<ToastState>,
+    sampling_state: Res<TextureSamplingState>,
+    texture_mode_state: Res<TextureModeState>,
+    render_device: Option<Res<RenderDevice>>,
+) {
+    let frame_count = sequence_state.frames.len();
+    if frame_count == 0 {
+        return;
+    }
+
+    if sequence_state.playing && sequence_state.fps > 0.0 {
+        sequence_state.elapsed += time.delta_secs();
+        let frame_duration = 1.0 / sequence_state.fps;
+        while sequence_state.elapsed >= frame_duration {
+            sequence_state.elapsed -= frame_duration;
+            if sequence_state.current_frame + 1 < frame_count {
+                sequence_state.current_frame += 1;
+            } else if sequence_state.looping {
+                sequence_state.current_frame = 0;
+            } else {
+                sequence_state.playing = false;
+                sequence_state.elapsed = 0.0;
+                break;
+            }
+        }
+    }
+    let frame_index = sequence_state.current_frame.min(frame_count - 1);
+    sequence_state.current_frame = frame_index;
+
+    // Collect finished decodes
+    let finished: Vec<(usize, Result<Image, String>)> = sequence_state
+        .decoding
+        .iter_mut()
+        .filter_map(|(&index, task)| check_ready(task).map(|result| (index, result)))
+        .collect();
+    for (index, result) in finished {
+        sequence_state.decoding.remove(&index);
+        match result {
+            Ok(image) => sequence_state.handles[index] = Some(images.add(image)),
+            Err(error) => {
+                let file_name = sequence_state.frames[index].file_name().unwrap_or_default().to_string_lossy();
+                toast_state.show_error(format!("Could not decode {file_name}: {error}"));
+                sequence_state.failed[index] = true;
+                sequence_state.playing = false;
+            }
+        }
+    }
+
+    // Evict frames outside the window (dropping the handle frees the image) and cancel their
+    // decodes, then start decoding the missing ones nearest the playhead first
+    let window = cache_window(frame_index, frame_count, sequence_state.looping);
+    for index in 0..frame_count {
+        if !window.contains(&index) {
+            sequence_state.handles[index] = None;
+            sequence_state.decoding.remove(&index);
+        }
+    }
+    let float32_filterable = render_device
+        .is_some_and(|device| device.features().contains(WgpuFeatures::FLOAT32_FILTERABLE));
+    let sampler = sampler_descriptor(&sampling_state.last, texture_mode_state.current);
+    let task_pool = AsyncComputeTaskPool::get();
+    for &index in &window {
+        if sequence_state.decoding.len() >= SEQUENCE_MAX_DECODES {
+            break;
+        }
+        if sequence_state.handles[index].is_some()
+            || sequence_state.failed[index]
+            || sequence_state.decoding.contains_key(&index)
+        {
+            continue;
+        }
+        let path = sequence_state.frames[index].clone();
+        let sampler = sampler.clone();
+        let task = task_pool.spawn(async move { decode_frame(path, float32_filterable, sampler) });
+        sequence_state.decoding.insert(index, task);
+    }
+
+    // Keep showing the last frame until the current one is decoded
+    if sequence_state.previous_frame == Some(frame_index) {
+        return;
+    }
+    let Some(handle) = sequence_state.handles[frame_index].clone() else {
+        return;
+    };
+    sequence_state.previous_frame = Some(frame_index);
+    let path = sequence_state.frames[frame_index].clone();
+    texture_source.set_file(&path, handle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_numbers_are_split_from_the_stem() {
+        assert_eq!(split_frame_number(Path::new("shots/frame_0012.png")), Some(("frame_".to_string(), 12)));
+        assert_eq!(split_frame_number(Path::new("0042.exr")), Some((String::new(), 42)));
+        assert_eq!(split_frame_number(Path::new("frame.png")), None);
+    }
+
+    #[test]
+    fn window_prefetches_ahead_and_keeps_a_few_behind() {
+        assert_eq!(cache_window(0, 20, true), (0..=SEQUENCE_PREFETCH_FRAMES).collect::<Vec<_>>());
+        assert_eq!(cache_window(10, 20, false), vec![10, 11, 12, 13, 14, 15, 16, 17, 18, 9, 8]);
+    }
+
+    #[test]
+    fn window_wraps_only_when_looping() {
+        assert_eq!(cache_window(18, 20, false), vec![18, 19, 17, 16]);
+        assert_eq!(cache_window(18, 20, true), vec![18, 19, 0, 1, 2, 3, 4, 5, 6, 17, 16]);
+    }
+
+    #[test]
+    fn short_sequences_list_each_frame_once() {
+        assert_eq!(cache_window(1, 3, true), vec![1, 2, 0]);
+        assert_eq!(cache_window(0, 1, true), vec![0]);
+    }
+}
@@ -0,0 +1,547 @@
+// systems/image_transform.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{EguiLayoutState, ImageSequenceState, ImageTransform, ImageTransformState, PlaneSize, ResampleFilter, RightCamera, TextureSourceState, ToastState, TexturedPlane};
+use crate::materials::TexturePlaneMaterial;
+use crate::systems::picking::{cursor_in_viewport, pick_plane, plane_point_to_world};
+use crate::systems::hdr::{linear_to_srgb, srgb_to_linear};
+use crate::systems::pixels::{channel_layout, ChannelType, PixelBuffer};
+
+const LANCZOS_LOBES: f32 = 3.0;
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < f32::EPSILON {
+        1.0
+    } else {
+        let x = x * std::f32::consts::PI;
+        x.sin() / x
+    }
+}
+
+// Source pixels and weights that make up each target pixel along one axis
+fn axis_weights(source_len: u32, target_len: u32, filter: ResampleFilter) -> Vec<Vec<(u32, f32)>> {
+    let scale = source_len as f32 / target_len as f32;
+    // When shrinking, Lanczos widens to cover every source pixel so it doesn't alias
+    let (support, stretch) = match filter {
+        ResampleFilter::Nearest | ResampleFilter::Bilinear => (1.0, 1.0),
+        ResampleFilter::Lanczos => (LANCZOS_LOBES * scale.max(1.0), scale.max(1.0)),
+    };
+
+    (0..target_len)
+        .map(|target| {
+            let center = (target as f32 + 0.5) * scale;
+            if filter == ResampleFilter::Nearest {
+                return vec![((center as u32).min(source_len - 1), 1.0)];
+            }
+
+            let mut weights: Vec<(u32, f32)> = Vec::new();
+            let first = (center - 0.5 - support).floor() as i32;
+            let last = (center - 0.5 + support).ceil() as i32;
+            for source in first..=last {
+                let distance = (source as f32 + 0.5 - center) / stretch;
+                let weight = match filter {
+                    ResampleFilter::Lanczos if distance.abs() < LANCZOS_LOBES => {
+                        sinc(distance) * sinc(distance / LANCZOS_LOBES)
+                    }
+                    ResampleFilter::Bilinear => (1.0 - distance.abs()).max(0.0),
+                    _ => 0.0,
+                };
+                if weight == 0.0 {
+                    continue;
+                }
+                // Edge pixels are repeated past the border
+                let source = source.clamp(0, source_len as i32 - 1) as u32;
+                match weights.iter_mut().find(|(index, _)| *index == source) {
+                    Some((_, total)) => *total += weight,
+                    None => weights.push((source, weight)),
+                }
+            }
+            let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+            for (_, weight) in &mut weights {
+                *weight /= total;
+            }
+            weights
+        })
+        .collect()
+}
+
+fn map_rgb(pixel: Vec4, convert: fn(f32) -> f32) -> Vec4 {
+    Vec3::from_array(pixel.truncate().to_array().map(convert)).extend(pixel.w)
+}
+
+fn resize(buffer: &PixelBuffer, size: UVec2, filter: ResampleFilter) -> PixelBuffer {
+    // Filtering encoded values darkens edges and fine detail, so sRGB images are resampled in
+    // linear light like the mip chain
+    let srgb = buffer.format.is_srgb();
+    let decoded;
+    let buffer = if srgb {
+        decoded = PixelBuffer {
+            size: buffer.size,
+            pixels: buffer.pixels.iter().map(|&pixel| map_rgb(pixel, srgb_to_linear)).collect(),
+            format: buffer.format,
+        };
+        &decoded
+    } else {
+        buffer
+    };
+
+    let source_size = buffer.size;
+    let columns = axis_weights(source_size.x, size.x, filter);
+    let rows = axis_weights(source_size.y, size.y, filter);
+
+    // Horizontal pass into an intermediate of the new width, then the vertical pass
+    let mut horizontal = Vec::with_capacity((size.x * source_size.y) as usize);
+    for y in 0..source_size.y {
+        for weights in &columns {
+            horizontal.push(weights.iter().map(|&(x, weight)| buffer.get(x, y) * weight).sum::<Vec4>());
+        }
+    }
+    let mut pixels = Vec::with_capacity((size.x * size.y) as usize);
+    for weights in &rows {
+        for x in 0..size.x {
+            pixels.push(
+                weights
+                    .iter()
+                    .map(|&(y, weight)| horizontal[(y * size.x + x) as usize] * weight)
+                    .sum::<Vec4>(),
+            );
+        }
+    }
+    if srgb {
+        for pixel in &mut pixels {
+            *pixel = map_rgb(*pixel, linear_to_srgb);
+        }
+    }
+    PixelBuffer { size, pixels, format: buffer.format }
+}
+
+// Builds a buffer of the given size where each pixel is read from source coordinates
+fn remap(buffer: &PixelBuffer, size: UVec2, source: impl Fn(u32, u32) -> (u32, u32)) -> PixelBuffer {
+    let mut pixels = Vec::with_capacity((size.x * size.y) as usize);
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let (source_x, source_y) = source(x, y);
+            pixels.push(buffer.get(source_x, source_y));
+        }
+    }
+    PixelBuffer { size, pixels, format: buffer.format }
+}
+
+fn transform_buffer(buffer: &PixelBuffer, transform: ImageTransform) -> PixelBuffer {
+    let UVec2 { x: width, y: height } = buffer.size;
+    match transform {
+        ImageTransform::Crop(rect) => remap(buffer, rect.size(), |x, y| (rect.min.x + x, rect.min.y + y)),
+        ImageTransform::Resize { size, filter } => resize(buffer, size, filter),
+        ImageTransform::RotateClockwise => remap(buffer, UVec2::new(height, width), |x, y| (y, height - 1 - x)),
+        ImageTransform::RotateCounterClockwise => remap(buffer, UVec2::new(height, width), |x, y| (width - 1 - y, x)),
+        ImageTransform::Rotate180 => remap(buffer, buffer.size, |x, y| (width - 1 - x, height - 1 - y)),
+        ImageTransform::FlipHorizontal => remap(buffer, buffer.size, |x, y| (width - 1 - x, y)),
+        ImageTransform::FlipVertical => remap(buffer, buffer.size, |x, y| (x, height - 1 - y)),
+    }
+}
+
+// Replaces the source image with the transformed copy. The result no longer matches the file on
+// disk, so it's detached from hot reload and the scale sidecar like a generated texture.
+pub fn apply_image_transform(
+    mut transform_state: ResMut<ImageTransformState>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+    mut toast_state: ResMut<ToastState>,
+) {
+    let Some(transform) = transform_state.pending.take() else {
+        return;
+    };
+    let Some(image) = texture_source.current.as_ref().and_then(|handle| images.get(handle)) else {
+        return;
+    };
+    let Some(buffer) = PixelBuffer::from_image(image) else {
+        toast_state.show_error(format!("{:?} textures can't be transformed", image.texture_descriptor.format));
+        return;
+    };
+
+    let mut transformed = transform_buffer(&buffer, transform).to_image();
+    transformed.sampler = image.sampler.clone();
+    sequence_state.clear();
+    // Further edits replace the edited copy in place instead of piling full-size copies into
+    // the library
+    match transform_state.edited.clone().filter(|edited| texture_source.current.as_ref() == Some(edited)) {
+        Some(edited) => {
+            let _ = images.insert(edited.id(), transformed);
+        }
+        None => {
+            let edited = images.add(transformed);
+            transform_state.edited = Some(edited.clone());
+            texture_source.current = Some(edited);
+        }
+    }
+    texture_source.file_path = None;
+    if !texture_source.path.ends_with(" (edited)") {
+        texture_source.path.push_str(" (edited)");
+    }
+    // Pixel coordinates from the old image no longer apply
+    transform_state.crop = None;
+    transform_state.sized_for = None;
+}
+
+// Only the encoders the image crate is built with
+fn export_format(path: &std::path::Path) -> Result<image::ImageFormat, String> {
+    match image::ImageFormat::from_path(path) {
+        Ok(format @ (image::ImageFormat::Png | image::ImageFormat::OpenExr)) => Ok(format),
+        _ => Err("only .png and .exr files are supported".to_string()),
+    }
+}
+
+// PNG stores 8-bit values: 8-bit formats keep their stored encoding and float (linear) formats
+// are sRGB-encoded. EXR stores linear floats, so sRGB formats are decoded.
+fn to_dynamic_image(buffer: &PixelBuffer, format: image::ImageFormat) -> image::DynamicImage {
+    let UVec2 { x: width, y: height } = buffer.size;
+    if format == image::ImageFormat::OpenExr {
+        let srgb = buffer.format.is_srgb();
+        return image::DynamicImage::ImageRgba32F(image::Rgba32FImage::from_fn(width, height, |x, y| {
+            let pixel = buffer.get(x, y);
+            image::Rgba(if srgb { map_rgb(pixel, srgb_to_linear) } else { pixel }.to_array())
+        }));
+    }
+    let float = !matches!(channel_layout(buffer.format), Some((ChannelType::U8, _)));
+    image::DynamicImage::ImageRgba8(image::RgbaImage::from_fn(width, height, |x, y| {
+        let pixel = buffer.get(x, y);
+        let pixel = if float { map_rgb(pixel, linear_to_srgb) } else { pixel };
+        image::Rgba(pixel.to_array().map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8))
+    }))
+}
+
+fn default_export_path(file_path: Option<&std::path::Path>, format: TextureFormat) -> String {
+    let extension = match channel_layout(format) {
+        Some((ChannelType::U8, _)) => "png",
+        _ => "exr",
+    };
+    match file_path.and_then(|path| path.file_stem().map(|stem| (path, stem))) {
+        Some((path, stem)) => path
+            .with_file_name(format!("{}_edited.{extension}", stem.to_string_lossy()))
+            .display()
+            .to_string(),
+        None => format!("export.{extension}"),
+    }
+}
+
+// Writes the image shown on the plane, adjustments and transforms included, as PNG or EXR
+// depending on the extension
+pub fn export_displayed_image(
+    mut transform_state: ResMut<ImageTransformState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    mut toast_state: ResMut<ToastState>,
+) {
+    if !transform_state.export_requested {
+        return;
+    }
+    transform_state.export_requested = false;
+    let Some(image) = texture_source.displayed().and_then(|handle| images.get(handle)) else {
+        return;
+    };
+    let Some(buffer) = PixelBuffer::from_image(image) else {
+        toast_state.show_error(format!("{:?} textures can't be exported", image.texture_descriptor.format));
+        return;
+    };
+
+    let path = std::path::PathBuf::from(transform_state.export_path.trim());
+    let format = match export_format(&path) {
+        Ok(format) => format,
+        Err(error) => {
+            toast_state.show_error(format!("Could not export image: {error}"));
+            return;
+        }
+    };
+    match to_dynamic_image(&buffer, format).save_with_format(&path, format) {
+        Ok(()) => {
+            info!("Exported image to {}", path.display());
+            transform_state.exported_to = Some(path);
+        }
+        Err(error) => toast_state.show_error(format!("Could not export image: {error}")),
+    }
+}
+
+// Drag on the plane to draw the crop rectangle, and outline it while it's set
+#[allow(clippy::too_many_arguments)]
+pub fn crop_picking(
+    mut contexts: EguiContexts,
+    window: Query<&Window>,
+    layout_state: Res<EguiLayoutState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut transform_state: ResMut<ImageTransformState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    materials: Res<Assets<TexturePlaneMaterial>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<RightCamera>>,
+    plane_query: Query<(&GlobalTransform, &PlaneSize, &MeshMaterial3d<TexturePlaneMaterial>), With<TexturedPlane>>,
+) {
+    if !transform_state.drawing_crop && transform_state.crop.is_none() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let (Ok(window), Ok((camera, camera_transform)), Ok((plane_transform, plane_size, material_3d))) =
+        (window.single(), camera_query.single(), plane_query.single())
+    else {
+        return;
+    };
+    let (Some(material), Some(image)) = (
+        materials.get(&material_3d.0),
+        texture_source.current.as_ref().and_then(|handle| images.get(handle)),
+    ) else {
+        return;
+    };
+    let image_size = image.size().as_vec2();
+    let uv_transform = material.base.uv_transform;
+
+    if transform_state.drawing_crop {
+        // Positions past the texture edges clamp to them, so the rectangle can reach the border
+        let cursor_pixel = window
+            .cursor_position()
+            .and_then(|cursor| pick_plane(camera, camera_transform, cursor, plane_transform, plane_size.0))
+            .map(|hit| (uv_transform.transform_point2(hit.uv) * image_size).clamp(Vec2::ZERO, image_size));
+
+        if mouse_buttons.just_pressed(MouseButton::Left)
+            && !ctx.is_pointer_over_area()
+            && cursor_in_viewport(window, &layout_state).is_some()
+        {
+            transform_state.crop_anchor = cursor_pixel;
+        }
+        if let (Some(anchor), Some(pixel)) = (transform_state.crop_anchor, cursor_pixel) {
+            let min = anchor.min(pixel).round().as_uvec2();
+            let max = anchor.max(pixel).round().as_uvec2();
+            transform_state.crop = (min.cmplt(max).all()).then(|| URect::from_corners(min, max));
+        }
+        if mouse_buttons.just_released(MouseButton::Left) && transform_state.crop_anchor.is_some() {
+            transform_state.crop_anchor = None;
+            transform_state.drawing_crop = false;
+        }
+    }
+
+    let Some(crop) = transform_state.crop else {
+        return;
+    };
+    // Texture pixels back to plane points through the inverse of the material's UV transform
+    let plane_uv_transform = uv_transform.inverse();
+    let corners: Vec<egui::Pos2> = [
+        crop.min.as_vec2(),
+        Vec2::new(crop.max.x as f32, crop.min.y as f32),
+        crop.max.as_vec2(),
+        Vec2::new(crop.min.x as f32, crop.max.y as f32),
+    ]
+    .into_iter()
+    .filter_map(|pixel| {
+        let plane_uv = plane_uv_transform.transform_point2(pixel / image_size);
+        let world = plane_point_to_world(plane_transform, (plane_uv - 0.5) * plane_size.0);
+        camera.world_to_viewport(camera_transform, world).ok()
+    })
+    .map(|position| egui::pos2(position.x, position.y))
+    .collect();
+    if corners.len() < 4 {
+        return;
+    }
+
+    let viewport = egui::Rect::from_min_max(
+        egui::pos2(layout_state.viewport_left, layout_state.viewport_top),
+        egui::pos2(layout_state.viewport_right, layout_state.viewport_bottom),
+    );
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("crop_rectangle")))
+        .with_clip_rect(viewport);
+    painter.add(egui::Shape::closed_line(corners, egui::Stroke::new(2.0, egui::Color32::from_rgb(0, 200, 255))));
+}
+
+pub fn image_transform_window(
+    mut contexts: EguiContexts,
+    mut transform_state: ResMut<ImageTransformState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+) {
+    if !transform_state.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Some((source_id, image_size, format)) = texture_source.current.as_ref().and_then(|handle| {
+        images.get(handle).map(|image| (handle.id(), image.size(), image.texture_descriptor.format))
+    }) else {
+        return;
+    };
+    if transform_state.sized_for != Some(source_id) {
+        transform_state.sized_for = Some(source_id);
+        transform_state.resize_size = image_size;
+        transform_state.export_path = default_export_path(texture_source.file_path.as_deref(), format);
+        transform_state.exported_to = None;
+    }
+
+    let mut open = transform_state.open;
+    egui::Window::new("Transform image")
+        .open(&mut open)
+        .default_width(260.0)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ui.label(format!("Current size: {} × {} px", image_size.x, image_size.y));
+            ui.separator();
+
+            ui.label("Rotate and flip");
+            ui.horizontal(|ui| {
+                let buttons = [
+                    ("⟲ 90°", ImageTransform::RotateCounterClockwise),
+                    ("⟳ 90°", ImageTransform::RotateClockwise),
+                    ("180°", ImageTransform::Rotate180),
+                    ("⬌", ImageTransform::FlipHorizontal),
+                    ("⬍", ImageTransform::FlipVertical),
+                ];
+                for (label, transform) in buttons {
+                    if ui.button(label).clicked() {
+                        transform_state.pending = Some(transform);
+                    }
+                }
+            });
+            ui.separator();
+
+            ui.label("Resize");
+            let previous_size = transform_state.resize_size;
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut transform_state.resize_size.x).range(1..=16384).suffix(" px"));
+                ui.label("×");
+                ui.add(egui::DragValue::new(&mut transform_state.resize_size.y).range(1..=16384).suffix(" px"));
+            });
+            if transform_state.keep_aspect {
+                let aspect = image_size.x as f32 / image_size.y as f32;
+                let size = &mut transform_state.resize_size;
+                if size.x != previous_size.x {
+                    size.y = ((size.x as f32 / aspect).round() as u32).max(1);
+                } else if size.y != previous_size.y {
+                    size.x = ((size.y as f32 * aspect).round() as u32).max(1);
+                }
+            }
+            ui.checkbox(&mut transform_state.keep_aspect, "Keep aspect ratio");
+            egui::ComboBox::from_label("Resampling")
+                .selected_text(transform_state.resample.label())
+                .show_ui(ui, |ui| {
+                    for filter in ResampleFilter::ALL {
+                        ui.selectable_value(&mut transform_state.resample, filter, filter.label());
+                    }
+                });
+            if ui
+                .add_enabled(transform_state.resize_size != image_size, egui::Button::new("Apply resize"))
+                .clicked()
+            {
+                transform_state.pending = Some(ImageTransform::Resize {
+                    size: transform_state.resize_size,
+                    filter: transform_state.resample,
+                });
+            }
+            ui.separator();
+
+            ui.label("Crop");
+            let draw_label = if transform_state.drawing_crop { "Drag on the plane…" } else { "Draw rectangle" };
+            if ui.button(draw_label).clicked() {
+                transform_state.drawing_crop = !transform_state.drawing_crop;
+                transform_state.crop_anchor = None;
+            }
+            if let Some(mut crop) = transform_state.crop {
+                let mut origin = crop.min;
+                let mut size = crop.size();
+                ui.horizontal(|ui| {
+                    ui.label("Origin");
+                    ui.add(egui::DragValue::new(&mut origin.x).range(0..=image_size.x - 1));
+                    ui.add(egui::DragValue::new(&mut origin.y).range(0..=image_size.y - 1));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Size");
+                    ui.add(egui::DragValue::new(&mut size.x).range(1..=image_size.x - origin.x));
+                    ui.add(egui::DragValue::new(&mut size.y).range(1..=image_size.y - origin.y));
+                });
+                crop = URect::from_corners(origin, (origin + size).min(image_size));
+                transform_state.crop = Some(crop);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Apply crop").clicked() {
+                        transform_state.pending = Some(ImageTransform::Crop(crop));
+                    }
+                    if ui.button("Clear").clicked() {
+                        transform_state.crop = None;
+                    }
+                });
+            }
+
+            // Browsers have no file system to write to
+            if cfg!(not(target_arch = "wasm32")) {
+                ui.separator();
+                ui.label("Export");
+                ui.text_edit_singleline(&mut transform_state.export_path);
+                if ui
+                    .add_enabled(!transform_state.export_path.trim().is_empty(), egui::Button::new("Export image"))
+                    .on_hover_text("Save the image on the plane, adjustments included, as .png or .exr")
+                    .clicked()
+                {
+                    transform_state.export_requested = true;
+                }
+                if let Some(path) = &transform_state.exported_to {
+                    ui.label(format!("Saved to {}", path.display()));
+                }
+            }
+        });
+    transform_state.open = open;
+    if !open {
+        transform_state.drawing_crop = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILTERS: [ResampleFilter; 3] = [ResampleFilter::Nearest, ResampleFilter::Bilinear, ResampleFilter::Lanczos];
+
+    #[test]
+    fn weights_are_normalized_and_in_range() {
+        for filter in FILTERS {
+            for (source_len, target_len) in [(7, 3), (3, 7), (16, 16), (1, 4), (100, 10)] {
+                for weights in axis_weights(source_len, target_len, filter) {
+                    let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+                    assert!((total - 1.0).abs() < 1e-5);
+                    assert!(weights.iter().all(|&(index, _)| index < source_len));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_picks_the_covering_pixel() {
+        assert_eq!(axis_weights(4, 2, ResampleFilter::Nearest), vec![vec![(1, 1.0)], vec![(3, 1.0)]]);
+    }
+
+    #[test]
+    fn same_size_is_an_identity() {
+        for filter in FILTERS {
+            for (target, weights) in axis_weights(9, 9, filter).into_iter().enumerate() {
+                let own = weights.iter().find(|&&(index, _)| index == target as u32).map(|&(_, weight)| weight);
+                assert!(own.is_some_and(|weight| (weight - 1.0).abs() < 1e-5));
+            }
+        }
+    }
+
+    #[test]
+    fn lanczos_edges_repeat_the_border_pixel() {
+        // Taps past the left edge fold onto pixel 0 instead of being dropped
+        let weights = &axis_weights(4, 8, ResampleFilter::Lanczos)[0];
+        assert_eq!(weights.iter().filter(|&&(index, _)| index == 0).count(), 1);
+        assert!(weights.iter().map(|&(index, _)| index).max() <= Some(3));
+    }
+
+    #[test]
+    fn lanczos_widens_when_shrinking() {
+        // Shrinking 10x stretches the three lobes on each side over ten source pixels each
+        let weights = &axis_weights(100, 10, ResampleFilter::Lanczos)[5];
+        assert!(weights.len() > 2 * LANCZOS_LOBES as usize * 5);
+    }
+}
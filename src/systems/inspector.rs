@@ -0,0 +1,87 @@
+// systems/inspector.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{EguiLayoutState, PixelInspectorState, PlaneSize, RightCamera, TextureSourceState, TextureViewDragState, TexturedPlane};
+use crate::materials::TexturePlaneMaterial;
+use crate::systems::picking::{cursor_in_viewport, pick_plane, plane_uv_to_texture_pixel};
+use crate::systems::sampling::is_8bit_format;
+
+#[allow(clippy::too_many_arguments)]
+pub fn pixel_inspector_tooltip(
+    mut contexts: EguiContexts,
+    window: Query<&Window>,
+    layout_state: Res<EguiLayoutState>,
+    inspector_state: Res<PixelInspectorState>,
+    drag_state: Res<TextureViewDragState>,
+    texture_source: Res<TextureSourceState>,
+    images: Res<Assets<Image>>,
+    materials: Res<Assets<TexturePlaneMaterial>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<RightCamera>>,
+    plane_query: Query<(&GlobalTransform, &PlaneSize, &MeshMaterial3d<TexturePlaneMaterial>), With<TexturedPlane>>,
+) {
+    if !inspector_state.enabled || drag_state.active.is_some() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if ctx.is_pointer_over_area() {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform)), Ok((plane_transform, plane_size, material_3d))) =
+        (window.single(), camera_query.single(), plane_query.single())
+    else {
+        return;
+    };
+    let Some(cursor) = cursor_in_viewport(window, &layout_state) else {
+        return;
+    };
+    let Some(hit) = pick_plane(camera, camera_transform, cursor, plane_transform, plane_size.0).filter(|hit| hit.is_inside()) else {
+        return;
+    };
+    let (Some(material), Some(image)) = (
+        materials.get(&material_3d.0),
+        texture_source.displayed().and_then(|handle| images.get(handle)),
+    ) else {
+        return;
+    };
+
+    let size = image.size();
+    let Some(pixel) = plane_uv_to_texture_pixel(material, size, hit.uv) else {
+        return;
+    };
+    let texel = pixel.floor().as_uvec2().min(size - UVec2::ONE);
+    let Ok(color) = image.get_color_at(texel.x, texel.y) else {
+        return;
+    };
+
+    let format = image.texture_descriptor.format;
+    let srgba = color.to_srgba();
+    let hsva = Hsva::from(color);
+    egui::Tooltip::always_open(ctx.clone(), egui::LayerId::background(), egui::Id::new("pixel_inspector"), egui::PopupAnchor::Pointer)
+        .gap(12.0)
+        .show(|ui| {
+            ui.label(format!("x {}  y {}", texel.x, texel.y));
+            // Show the values as stored: bytes for 8-bit formats, linear floats otherwise
+            if is_8bit_format(format) {
+                let stored = if format.is_srgb() { srgba.to_f32_array() } else { color.to_linear().to_f32_array() };
+                let [r, g, b, a] = stored.map(|value| (value * 255.0).round() as u8);
+                ui.label(format!("RGBA {r} {g} {b} {a}"));
+            } else {
+                let [r, g, b, a] = color.to_linear().to_f32_array();
+                ui.label(format!("RGBA {r:.3} {g:.3} {b:.3} {a:.3}"));
+            }
+            ui.label(srgba.to_hex());
+            ui.label(format!(
+                "HSV {:.0}° {:.0}% {:.0}%",
+                hsva.hue,
+                hsva.saturation * 100.0,
+                hsva.value * 100.0
+            ));
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 12.0), egui::Sense::hover());
+            let [r, g, b] = srgba.to_u8_array_no_alpha();
+            ui.painter().rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+        });
+}
@@ -1,10 +1,49 @@
 // systems/mod.rs
 // Copyright (C) 2026 vecnode
 
+pub mod adjustments;
+pub mod atlas;
+pub mod calibration;
+pub mod channels;
+pub mod clipboard;
+pub mod compare;
 pub mod egui_ui;
+pub mod file_browser;
+pub mod filters;
+pub mod generators;
 pub mod grid;
+pub mod hdr;
+pub mod histogram;
+pub mod hot_reload;
+pub mod image_sequence;
+pub mod image_transform;
+pub mod inspector;
+pub mod picking;
+pub mod pixels;
+pub mod sampling;
 pub mod texture;
+pub mod texture_library;
+pub mod texture_source;
+pub mod texture_view;
 
+pub use adjustments::{adjustments_window, apply_adjustment_stack};
+pub use atlas::animate_atlas_cells;
+pub use calibration::{apply_texture_scale, calibration_picking};
+pub use channels::{apply_channel_view, apply_color_visualization};
+pub use clipboard::paste_clipboard_image;
+pub use compare::apply_compare_settings;
 pub use egui_ui::egui_controls_ui;
+pub use file_browser::file_browser_window;
+pub use generators::{apply_texture_generator, texture_generator_window};
 pub use grid::update_grid_dimensions;
-pub use texture::update_texture_aspect_ratio;
\ No newline at end of file
+pub use hdr::{apply_tone_mapping, prepare_display_texture};
+pub use histogram::{histogram_window, update_image_statistics};
+pub use hot_reload::hot_reload_texture;
+pub use image_sequence::advance_image_sequence;
+pub use image_transform::{apply_image_transform, crop_picking, export_displayed_image, image_transform_window};
+pub use inspector::pixel_inspector_tooltip;
+pub use sampling::apply_texture_sampling;
+pub use texture::update_texture_aspect_ratio;
+pub use texture_library::track_texture_library;
+pub use texture_source::{handle_dropped_files, tick_toast};
+pub use texture_view::texture_view_interaction;
@@ -0,0 +1,68 @@
+// systems/picking.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use crate::components::EguiLayoutState;
+use crate::materials::TexturePlaneMaterial;
+
+// Where the cursor ray meets a textured plane, in meters from the plane center with y pointing
+// down the texture, plus the matching plane UV. Hits outside the plane rectangle are kept so
+// drags can continue past its edges.
+pub struct PlaneHit {
+    pub point: Vec2,
+    pub uv: Vec2,
+}
+
+impl PlaneHit {
+    pub fn is_inside(&self) -> bool {
+        self.uv.cmpge(Vec2::ZERO).all() && self.uv.cmple(Vec2::ONE).all()
+    }
+}
+
+// Cursor position in window coordinates, if it is over the 3D viewport
+pub fn cursor_in_viewport(window: &Window, layout_state: &EguiLayoutState) -> Option<Vec2> {
+    let cursor = window.cursor_position()?;
+    let inside_viewport = cursor.x >= layout_state.viewport_left
+        && cursor.x <= layout_state.viewport_right
+        && cursor.y >= layout_state.viewport_top
+        && cursor.y <= layout_state.viewport_bottom;
+    inside_viewport.then_some(cursor)
+}
+
+pub fn pick_plane(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    cursor: Vec2,
+    plane_transform: &GlobalTransform,
+    plane_size: Vec2,
+) -> Option<PlaneHit> {
+    let ray = camera.viewport_to_world(camera_transform, cursor).ok()?;
+    let distance = ray.intersect_plane(
+        plane_transform.translation(),
+        InfinitePlane3d::new(plane_transform.back()),
+    )?;
+    let local = plane_transform.affine().inverse().transform_point3(ray.get_point(distance));
+    let point = Vec2::new(local.x, -local.y);
+    Some(PlaneHit {
+        point,
+        uv: point / plane_size + 0.5,
+    })
+}
+
+// Inverse of the mapping in pick_plane
+pub fn plane_point_to_world(plane_transform: &GlobalTransform, point: Vec2) -> Vec3 {
+    plane_transform.transform_point(Vec3::new(point.x, -point.y, 0.0))
+}
+
+// Texture position in pixels (not rounded) shown at a plane UV, or None over the matte. Same
+// mapping the shader uses: the material's UV transform takes plane UVs to texture UVs, and
+// anything outside the content rect is matte rather than texture.
+pub fn plane_uv_to_texture_pixel(material: &TexturePlaneMaterial, image_size: UVec2, plane_uv: Vec2) -> Option<Vec2> {
+    let uv = material.base.uv_transform.transform_point2(plane_uv);
+    let content_rect = material.extension.content_rect;
+    if uv.cmplt(content_rect.xy()).any() || uv.cmpgt(content_rect.zw()).any() {
+        return None;
+    }
+    // Only tiling has an unbounded rect; wrap like its repeating sampler does
+    Some(uv.rem_euclid(Vec2::ONE) * image_size.as_vec2())
+}
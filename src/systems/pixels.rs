@@ -0,0 +1,149 @@
+// systems/pixels.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::systems::hdr::{linear_to_srgb, srgb_to_linear};
+
+#[derive(Clone, Copy)]
+pub enum ChannelType {
+    U8,
+    F16,
+    F32,
+}
+
+impl ChannelType {
+    pub fn size(self) -> usize {
+        match self {
+            ChannelType::U8 => 1,
+            ChannelType::F16 => 2,
+            ChannelType::F32 => 4,
+        }
+    }
+}
+
+// Uncompressed formats whose pixels can be read and written on the CPU
+pub fn channel_layout(format: TextureFormat) -> Option<(ChannelType, usize)> {
+    match format {
+        TextureFormat::R8Unorm => Some((ChannelType::U8, 1)),
+        TextureFormat::Rg8Unorm => Some((ChannelType::U8, 2)),
+        TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Bgra8Unorm
+        | TextureFormat::Bgra8UnormSrgb => Some((ChannelType::U8, 4)),
+        TextureFormat::R16Float => Some((ChannelType::F16, 1)),
+        TextureFormat::Rgba16Float => Some((ChannelType::F16, 4)),
+        TextureFormat::R32Float => Some((ChannelType::F32, 1)),
+        TextureFormat::Rgba32Float => Some((ChannelType::F32, 4)),
+        _ => None,
+    }
+}
+
+pub fn read_channels(bytes: &[u8], channel_type: ChannelType, channels: usize, srgb: bool) -> Vec<f32> {
+    bytes
+        .chunks_exact(channel_type.size())
+        .enumerate()
+        .map(|(index, bytes)| {
+            let value = match channel_type {
+                ChannelType::U8 => bytes[0] as f32 / 255.0,
+                ChannelType::F16 => half::f16::from_le_bytes([bytes[0], bytes[1]]).to_f32(),
+                ChannelType::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            };
+            // Alpha is never sRGB-encoded
+            if srgb && index % channels < 3 {
+                srgb_to_linear(value)
+            } else {
+                value
+            }
+        })
+        .collect()
+}
+
+pub fn write_channels(values: &[f32], channel_type: ChannelType, channels: usize, srgb: bool, out: &mut Vec<u8>) {
+    for (index, &value) in values.iter().enumerate() {
+        let value = if srgb && index % channels < 3 {
+            linear_to_srgb(value)
+        } else {
+            value
+        };
+        match channel_type {
+            ChannelType::U8 => out.push((value.clamp(0.0, 1.0) * 255.0).round() as u8),
+            ChannelType::F16 => out.extend_from_slice(&half::f16::from_f32(value).to_le_bytes()),
+            ChannelType::F32 => out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+}
+
+
+// Base level of an image expanded to RGBA, in the image's own encoding (sRGB values stay
+// sRGB-encoded), so CPU edits behave like they would in an image editor.
+#[derive(Clone)]
+pub struct PixelBuffer {
+    pub size: UVec2,
+    pub pixels: Vec<Vec4>,
+    pub format: TextureFormat,
+}
+
+impl PixelBuffer {
+    pub fn from_image(image: &Image) -> Option<Self> {
+        let format = image.texture_descriptor.format;
+        let (channel_type, channels) = channel_layout(format)?;
+        let size = image.size();
+        let base_len = (size.x * size.y) as usize * channels * channel_type.size();
+        let data = image.data.as_ref().filter(|data| data.len() >= base_len)?;
+        let values = read_channels(&data[..base_len], channel_type, channels, false);
+        let bgra = is_bgra(format);
+
+        let pixels = values
+            .chunks_exact(channels)
+            .map(|texel| match texel {
+                [r] => Vec4::new(*r, 0.0, 0.0, 1.0),
+                [r, g] => Vec4::new(*r, *g, 0.0, 1.0),
+                [b, g, r, a] if bgra => Vec4::new(*r, *g, *b, *a),
+                _ => Vec4::from_slice(texel),
+            })
+            .collect();
+        Some(Self { size, pixels, format })
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Vec4 {
+        self.pixels[(y * self.size.x + x) as usize]
+    }
+
+    // Reads with coordinates clamped to the edge
+    pub fn get_clamped(&self, x: i32, y: i32) -> Vec4 {
+        let x = x.clamp(0, self.size.x as i32 - 1) as u32;
+        let y = y.clamp(0, self.size.y as i32 - 1) as u32;
+        self.get(x, y)
+    }
+
+    pub fn to_image(&self) -> Image {
+        let (channel_type, channels) =
+            channel_layout(self.format).expect("pixel buffers only hold CPU-readable formats");
+        let bgra = is_bgra(self.format);
+        let mut values = Vec::with_capacity(self.pixels.len() * channels);
+        for pixel in &self.pixels {
+            let texel = if bgra { Vec4::new(pixel.z, pixel.y, pixel.x, pixel.w) } else { *pixel };
+            values.extend_from_slice(&texel.to_array()[..channels]);
+        }
+        let mut data = Vec::with_capacity(values.len() * channel_type.size());
+        write_channels(&values, channel_type, channels, false, &mut data);
+
+        Image::new(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            self.format,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+fn is_bgra(format: TextureFormat) -> bool {
+    matches!(format, TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb)
+}
@@ -0,0 +1,134 @@
+// systems/sampling.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::image::{ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor};
+use bevy::render::render_resource::TextureFormat;
+use crate::components::{TextureMode, TextureModeState, TextureSampling, TextureSamplingState, TextureSourceState};
+use crate::systems::pixels::{channel_layout, read_channels, write_channels, ChannelType};
+
+pub fn supports_mipmaps(format: TextureFormat) -> bool {
+    channel_layout(format).is_some()
+}
+
+pub fn is_8bit_format(format: TextureFormat) -> bool {
+    matches!(channel_layout(format), Some((ChannelType::U8, _)))
+}
+
+// Appends a full mip chain (2x2 box filter) after the base level. Returns false if the format
+// isn't supported.
+pub fn generate_mipmaps(image: &mut Image) -> bool {
+    let format = image.texture_descriptor.format;
+    let Some((channel_type, channels)) = channel_layout(format) else {
+        return false;
+    };
+    let mut size = image.size();
+    let Some(data) = image.data.as_mut() else {
+        return false;
+    };
+    let srgb = format.is_srgb();
+
+    let base_len = (size.x * size.y) as usize * channels * channel_type.size();
+    data.truncate(base_len);
+    let mut level = read_channels(data, channel_type, channels, srgb);
+    let mut level_count = 1;
+
+    while size.x > 1 || size.y > 1 {
+        let next_size = (size / 2).max(UVec2::ONE);
+        let mut next = vec![0.0; (next_size.x * next_size.y) as usize * channels];
+        for y in 0..next_size.y {
+            for x in 0..next_size.x {
+                let target = (y * next_size.x + x) as usize * channels;
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    // Odd sizes reuse the last row/column
+                    let source_x = (x * 2 + dx).min(size.x - 1);
+                    let source_y = (y * 2 + dy).min(size.y - 1);
+                    let source = (source_y * size.x + source_x) as usize * channels;
+                    for channel in 0..channels {
+                        next[target + channel] += level[source + channel] * 0.25;
+                    }
+                }
+            }
+        }
+        write_channels(&next, channel_type, channels, srgb, data);
+        level = next;
+        size = next_size;
+        level_count += 1;
+    }
+
+    image.texture_descriptor.mip_level_count = level_count;
+    true
+}
+
+pub fn strip_mipmaps(image: &mut Image) {
+    let Some((channel_type, channels)) = channel_layout(image.texture_descriptor.format) else {
+        return;
+    };
+    let base_len = (image.width() * image.height()) as usize * channels * channel_type.size();
+    if let Some(data) = image.data.as_mut() {
+        data.truncate(base_len);
+    }
+    image.texture_descriptor.mip_level_count = 1;
+}
+
+pub fn sampler_descriptor(sampling: &TextureSampling, texture_mode: TextureMode) -> ImageSamplerDescriptor {
+    // Tiling needs a repeating sampler; every other mode clamps so UVs outside [0,1] don't wrap
+    let address_mode = if texture_mode == TextureMode::Tile {
+        ImageAddressMode::Repeat
+    } else {
+        ImageAddressMode::ClampToEdge
+    };
+    let filter = if sampling.nearest {
+        ImageFilterMode::Nearest
+    } else {
+        ImageFilterMode::Linear
+    };
+    ImageSamplerDescriptor {
+        address_mode_u: address_mode,
+        address_mode_v: address_mode,
+        mag_filter: filter,
+        min_filter: filter,
+        mipmap_filter: filter,
+        // Anisotropic filtering requires every filter to be linear
+        anisotropy_clamp: if sampling.nearest { 1 } else { sampling.anisotropy.max(1) },
+        ..ImageSamplerDescriptor::linear()
+    }
+}
+
+pub fn apply_texture_sampling(
+    texture_source: Res<TextureSourceState>,
+    texture_mode_state: Res<TextureModeState>,
+    mut sampling_state: ResMut<TextureSamplingState>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // Settings belong to the source image but apply to whichever copy is displayed
+    let (Some(source), Some(handle)) = (&texture_source.current, texture_source.displayed()) else {
+        return;
+    };
+    let sampling = *sampling_state.settings_mut(source.id());
+    if sampling_state.last != sampling {
+        sampling_state.last = sampling;
+    }
+
+    let Some(image) = images.get(handle) else {
+        return;
+    };
+    let descriptor = sampler_descriptor(&sampling, texture_mode_state.current);
+    let has_mipmaps = image.texture_descriptor.mip_level_count > 1;
+    let wants_mipmaps = sampling.mipmaps && supports_mipmaps(image.texture_descriptor.format);
+    let sampler_matches = matches!(&image.sampler, ImageSampler::Descriptor(current) if *current == descriptor);
+    // Only touch the asset when needed, since every change re-uploads it and triggers a refit
+    if sampler_matches && has_mipmaps == wants_mipmaps {
+        return;
+    }
+
+    let Some(image) = images.get_mut(handle) else {
+        return;
+    };
+    if wants_mipmaps && !has_mipmaps {
+        generate_mipmaps(image);
+    } else if !wants_mipmaps && has_mipmaps {
+        strip_mipmaps(image);
+    }
+    image.sampler = ImageSampler::Descriptor(descriptor);
+}
@@ -3,102 +3,268 @@
 
 use bevy::prelude::*;
 use bevy::math::Affine2;
-use crate::components::{TexturedPlane, AspectRatioState, GridState, TextureModeState, TextureMode};
+use crate::components::{TexturedPlane, AspectRatioState, AtlasState, GridState, PlaneLayout, PlaneLayoutState, PlaneSize, ScaleCalibrationState, TextureModeState, TextureMode, TextureSourceState, TextureViewTransform};
+use crate::constants::{GRID_SPACING, PLANE_MIN_SIZE};
+use crate::materials::{TexturePlaneExtension, TexturePlaneMaterial};
+use crate::systems::atlas::atlas_cell_rect;
 
+// Maps plane UVs to texture UVs for the given fit mode. Modes that keep the texture's aspect
+// compute the fraction of the plane the texture covers and center it, so UVs outside that
+// area fall outside [0,1].
+pub fn fit_uv_transform(
+    mode: TextureMode,
+    texture_size: Vec2,
+    plane_size: Vec2,
+    tile_repeat: Vec2,
+    pixels_per_meter: f32,
+) -> Affine2 {
+    let texture_aspect = texture_size.x / texture_size.y;
+    let plane_aspect = plane_size.x / plane_size.y;
+    let fit_width = Vec2::new(1.0, plane_aspect / texture_aspect);
+    let fit_height = Vec2::new(texture_aspect / plane_aspect, 1.0);
+
+    let coverage = match mode {
+        TextureMode::Stretch => return Affine2::IDENTITY,
+        TextureMode::Tile => return Affine2::from_scale(tile_repeat),
+        // Letterbox: the whole texture stays visible
+        TextureMode::Normal if texture_aspect > plane_aspect => fit_width,
+        TextureMode::Normal => fit_height,
+        // Cover: the texture fills the plane and the overflow is cropped
+        TextureMode::Fill if texture_aspect > plane_aspect => fit_height,
+        TextureMode::Fill => fit_width,
+        TextureMode::FitWidth => fit_width,
+        TextureMode::FitHeight => fit_height,
+        TextureMode::Center => texture_size / pixels_per_meter / plane_size,
+    };
+
+    // uv_texture = (uv_plane - offset) / coverage, with the offset centering the texture
+    let offset = (Vec2::ONE - coverage) * 0.5;
+    Affine2::from_scale_angle_translation(Vec2::ONE / coverage, 0.0, -offset / coverage)
+}
+
+// Rounds a manually sized plane to whole grid cells and moves its corner onto the nearest grid
+// lines. Lines sit at half-cell offsets on odd-sized grids, so the offset comes from the grid.
+fn snap_plane_to_grid(layout: &mut PlaneLayout, grid_state: &GridState) {
+    if !layout.fit_to_grid {
+        layout.size = (layout.size / GRID_SPACING).round().max(Vec2::ONE) * GRID_SPACING;
+    }
+    let line_offset = Vec2::new(grid_state.size_x as f32, grid_state.size_z as f32) * 0.5;
+    let corner = layout.position - layout.size * 0.5;
+    let snapped_corner = ((corner + line_offset) / GRID_SPACING).round() * GRID_SPACING - line_offset;
+    layout.position = snapped_corner + layout.size * 0.5;
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn update_texture_aspect_ratio(
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<TexturePlaneMaterial>>,
     mut aspect_ratio_state: ResMut<AspectRatioState>,
     mut texture_mode_state: ResMut<TextureModeState>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut atlas_state: ResMut<AtlasState>,
+    mut image_events: MessageReader<AssetEvent<Image>>,
     grid_state: Res<GridState>,
+    mut plane_layout_state: ResMut<PlaneLayoutState>,
+    mut calibration: ResMut<ScaleCalibrationState>,
     images: Res<Assets<Image>>,
-    mut plane_query: Query<(&mut Mesh3d, &MeshMaterial3d<StandardMaterial>), With<TexturedPlane>>,
+    mut plane_query: Query<(&mut Mesh3d, &MeshMaterial3d<TexturePlaneMaterial>, &mut PlaneSize, Ref<TextureViewTransform>, &mut Transform), With<TexturedPlane>>,
 ) {
+    // Tiling would repeat the whole sheet rather than the selected cell
+    if atlas_state.current.enabled && texture_mode_state.current == TextureMode::Tile {
+        texture_mode_state.current = TextureMode::Normal;
+    }
+
     // Check if aspect ratio changed or grid size changed
-    let aspect_ratio_changed = aspect_ratio_state.current != aspect_ratio_state.previous;
-    let grid_size_changed = grid_state.size_z != grid_state.previous_size_z;
-    let texture_mode_changed = texture_mode_state.current != texture_mode_state.previous;
+    let aspect_ratio_changed = aspect_ratio_state.current != aspect_ratio_state.previous
+        || aspect_ratio_state.custom != aspect_ratio_state.previous_custom;
+    // Width matters too: snapping follows the grid lines, which shift with the grid's parity
+    let grid_size_changed = grid_state.size_z != grid_state.previous_size_z
+        || grid_state.size_x != grid_state.previous_size_x;
+    let plane_layout_changed = plane_layout_state.current != plane_layout_state.previous;
+    let scale_changed = calibration.pixels_per_meter != calibration.previous_pixels_per_meter;
+    let texture_mode_changed = texture_mode_state.current != texture_mode_state.previous
+        || texture_mode_state.tile_repeat != texture_mode_state.previous_tile_repeat
+        || texture_mode_state.matte != texture_mode_state.previous_matte;
+    let atlas_changed = atlas_state.current != atlas_state.previous;
+    let texture_source_changed = texture_source.displayed() != texture_source.previous.as_ref();
+    let view_changed = plane_query.iter().any(|(_, _, _, view, _)| view.is_changed());
+
+    // Re-run the fit once the displayed texture finishes loading, since its dimensions are unknown until then
+    let current_texture_id = texture_source.displayed().map(Handle::id);
+    let mut texture_loaded = false;
+    for event in image_events.read() {
+        if let Some(id) = current_texture_id
+            && (event.is_loaded_with_dependencies(id) || event.is_added(id) || event.is_modified(id))
+        {
+            texture_loaded = true;
+        }
+    }
     
-    if aspect_ratio_changed || grid_size_changed || texture_mode_changed {
+    if aspect_ratio_changed || grid_size_changed || texture_mode_changed || texture_source_changed || texture_loaded || atlas_changed || view_changed || plane_layout_changed || scale_changed {
         if aspect_ratio_changed {
             aspect_ratio_state.previous = aspect_ratio_state.current;
+            aspect_ratio_state.previous_custom = aspect_ratio_state.custom;
         }
         if texture_mode_changed {
             texture_mode_state.previous = texture_mode_state.current;
+            texture_mode_state.previous_tile_repeat = texture_mode_state.tile_repeat;
+            texture_mode_state.previous_matte = texture_mode_state.matte;
+        }
+        if atlas_changed {
+            atlas_state.previous = atlas_state.current;
+        }
+        if scale_changed {
+            calibration.previous_pixels_per_meter = calibration.pixels_per_meter;
+        }
+        if texture_source_changed {
+            texture_source.previous = texture_source.displayed().cloned();
         }
         
-        // Get grid size
-        let grid_z = grid_state.size_z as f32;
-        
-        // Calculate plane dimensions based on aspect ratio
-        let (size_x, size_z) = match aspect_ratio_state.current {
-            crate::components::AspectRatio::Ratio16_9 => {
-                // 16:9 aspect ratio: width = height * (16/9)
-                let size_x = grid_z * (16.0 / 9.0);
-                (size_x, grid_z)
-            }
-            crate::components::AspectRatio::Square => {
-                // Square (1:1) aspect ratio
-                (grid_z, grid_z)
-            }
-        };
-        
+        // With a sprite sheet, the selected cell takes the place of the whole texture
+        let texture_rects = texture_source
+            .displayed()
+            .and_then(|handle| images.get(handle))
+            .map(|image| {
+                let image_size = image.size().as_vec2();
+                let (cell_min, cell_size) = atlas_cell_rect(&atlas_state.current, image_size)
+                    .unwrap_or((Vec2::ZERO, image_size));
+                (image_size, cell_min, cell_size)
+            });
+
+        let mut layout = plane_layout_state.current;
+        if layout.fit_to_grid {
+            let grid_size = Vec2::new(grid_state.size_x as f32, grid_state.size_z as f32);
+            let ratio = aspect_ratio_state
+                .current
+                .ratio(aspect_ratio_state.custom, texture_rects.map(|(_, _, cell_size)| cell_size));
+            layout.size = if layout.fit_inside_grid {
+                // Largest plane with the aspect ratio that fits inside the grid: the constrained
+                // side spans the grid and the other stays within it
+                let scale = (grid_size.x / ratio).min(grid_size.y);
+                Vec2::new(ratio * scale, scale)
+            } else if ratio >= 1.0 {
+                // The shorter side spans the grid depth, so landscape ratios grow in width and
+                // portrait ratios grow in depth
+                Vec2::new(grid_size.y * ratio, grid_size.y)
+            } else {
+                Vec2::new(grid_size.y, grid_size.y / ratio)
+            };
+        } else {
+            layout.size = layout.size.max(Vec2::splat(PLANE_MIN_SIZE));
+        }
+        if layout.snap_to_grid {
+            snap_plane_to_grid(&mut layout, &grid_state);
+        }
+        // Write back the effective layout so the panel shows the fitted or snapped values
+        plane_layout_state.current = layout;
+        plane_layout_state.previous = layout;
+
         // Update the plane mesh and material for each textured plane
-        for (mut mesh_3d, material_3d) in plane_query.iter_mut() {
-            // Update mesh dimensions
-            let new_mesh = meshes.add(Rectangle::new(size_x, size_z));
-            mesh_3d.0 = new_mesh;
+        let new_plane_size = layout.size;
+        for (mut mesh_3d, material_3d, mut plane_size, view, mut transform) in plane_query.iter_mut() {
+            transform.translation.x = layout.position.x;
+            transform.translation.z = layout.position.y;
+
+            // Update mesh dimensions. Pan and zoom refit every frame while dragging, so the
+            // mesh is only rebuilt when its size actually changes.
+            if plane_size.0 != new_plane_size {
+                mesh_3d.0 = meshes.add(Rectangle::from_size(new_plane_size));
+                plane_size.0 = new_plane_size;
+            }
             
             // Update material based on texture mode
             if let Some(material) = materials.get_mut(&material_3d.0) {
                 // Ensure material remains unlit to display texture exactly as in the image
-                material.unlit = true;
+                material.base.unlit = true;
+
+                if texture_source_changed {
+                    material.base.base_color_texture = texture_source.displayed().cloned();
+                }
+
+                material.extension.matte_color = texture_mode_state.matte.linear_color();
                 
-                if let Some(texture_handle) = &material.base_color_texture
-                    && let Some(image) = images.get(texture_handle)
-                {
-                    let texture_width = image.width() as f32;
-                    let texture_height = image.height() as f32;
-                    let texture_aspect = texture_width / texture_height;
-                    let plane_aspect = size_x / size_z;
-                    
-                    match texture_mode_state.current {
-                        TextureMode::Normal => {
-                            // Maintain texture's original aspect ratio and center it
-                            let (scale_x, scale_z, offset_x, offset_z) = if texture_aspect > plane_aspect {
-                                // Texture is wider relative to its height than the plane
-                                // Fit texture to plane's width, scale height proportionally, center vertically
-                                let scale = size_x / texture_width; // Scale to fit width
-                                let scaled_height = texture_height * scale;
-                                let scale_z = scaled_height / size_z; // UV scale in Z direction
-                                let offset_z = (1.0 - scale_z) * 0.5; // Center vertically in UV space
-                                (1.0, scale_z, 0.0, offset_z)
-                            } else {
-                                // Texture is taller relative to its width than the plane
-                                // Fit texture to plane's height, scale width proportionally, center horizontally
-                                let scale = size_z / texture_height; // Scale to fit height
-                                let scaled_width = texture_width * scale;
-                                let scale_x = scaled_width / size_x; // UV scale in X direction
-                                let offset_x = (1.0 - scale_x) * 0.5; // Center horizontally in UV space
-                                (scale_x, 1.0, offset_x, 0.0)
-                            };
-                            
-                            // Create UV transform using Affine2 to scale and offset texture
-                            // Affine2 represents a 2D affine transformation (scale + translation)
-                            let scale = Vec2::new(scale_x, scale_z);
-                            let translation = Vec2::new(offset_x, offset_z);
-                            let uv_transform = Affine2::from_scale_angle_translation(scale, 0.0, translation);
-                            
-                            // Update material with UV transform
-                            material.uv_transform = uv_transform;
-                        }
-                        TextureMode::Stretch => {
-                            // Stretch texture to fill the plane (default behavior)
-                            material.uv_transform = Affine2::IDENTITY;
-                        }
-                    }
+                if let Some((image_size, cell_min, cell_size)) = texture_rects {
+                    let cell_transform = Affine2::from_scale_angle_translation(
+                        cell_size / image_size,
+                        0.0,
+                        cell_min / image_size,
+                    );
+
+                    let fit_transform = fit_uv_transform(
+                        texture_mode_state.current,
+                        cell_size,
+                        new_plane_size,
+                        texture_mode_state.tile_repeat,
+                        calibration.pixels_per_meter,
+                    );
+
+                    // Undo the user's pan/zoom/rotation, fit, then map the fitted [0,1] range into the atlas cell
+                    material.base.uv_transform = cell_transform * fit_transform * view.uv_transform(new_plane_size);
+
+                    // Outside the cell the shader draws the matte instead of clamped edge texels. Only
+                    // tiling keeps sampling past it; the other modes can reach it by letterboxing or
+                    // by zooming out.
+                    material.extension.content_rect = if texture_mode_state.current != TextureMode::Tile {
+                        let cell_max = cell_min + cell_size;
+                        Vec4::new(
+                            cell_min.x / image_size.x,
+                            cell_min.y / image_size.y,
+                            cell_max.x / image_size.x,
+                            cell_max.y / image_size.y,
+                        )
+                    } else {
+                        TexturePlaneExtension::UNBOUNDED
+                    };
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture_uv(mode: TextureMode, texture_size: Vec2, plane_size: Vec2, plane_uv: Vec2) -> Vec2 {
+        fit_uv_transform(mode, texture_size, plane_size, Vec2::ONE, 100.0).transform_point2(plane_uv)
+    }
+
+    #[test]
+    fn stretch_maps_plane_uvs_directly() {
+        let texture = Vec2::new(200.0, 100.0);
+        let transform = fit_uv_transform(TextureMode::Stretch, texture, Vec2::splat(10.0), Vec2::ONE, 100.0);
+        assert_eq!(transform, Affine2::IDENTITY);
+    }
+
+    #[test]
+    fn normal_letterboxes_a_wide_texture() {
+        let (texture, plane) = (Vec2::new(200.0, 100.0), Vec2::splat(10.0));
+        assert!(texture_uv(TextureMode::Normal, texture, plane, Vec2::splat(0.5)).abs_diff_eq(Vec2::splat(0.5), 1e-6));
+        // The texture covers the middle half of the plane's height
+        assert!(texture_uv(TextureMode::Normal, texture, plane, Vec2::new(0.0, 0.25)).abs_diff_eq(Vec2::ZERO, 1e-6));
+        assert!(texture_uv(TextureMode::Normal, texture, plane, Vec2::new(1.0, 0.75)).abs_diff_eq(Vec2::ONE, 1e-6));
+    }
+
+    #[test]
+    fn fill_crops_a_wide_texture() {
+        let (texture, plane) = (Vec2::new(200.0, 100.0), Vec2::splat(10.0));
+        // Only the middle half of the texture's width is visible
+        assert!(texture_uv(TextureMode::Fill, texture, plane, Vec2::ZERO).abs_diff_eq(Vec2::new(0.25, 0.0), 1e-6));
+        assert!(texture_uv(TextureMode::Fill, texture, plane, Vec2::ONE).abs_diff_eq(Vec2::new(0.75, 1.0), 1e-6));
+    }
+
+    #[test]
+    fn center_uses_the_physical_size() {
+        // 100 px at 100 px/m is 1 m, half of the 2 m plane
+        let (texture, plane) = (Vec2::splat(100.0), Vec2::splat(2.0));
+        assert!(texture_uv(TextureMode::Center, texture, plane, Vec2::splat(0.25)).abs_diff_eq(Vec2::ZERO, 1e-6));
+        assert!(texture_uv(TextureMode::Center, texture, plane, Vec2::splat(0.75)).abs_diff_eq(Vec2::ONE, 1e-6));
+    }
+
+    #[test]
+    fn tile_repeats_the_texture() {
+        let repeat = Vec2::new(3.0, 2.0);
+        let transform = fit_uv_transform(TextureMode::Tile, Vec2::splat(64.0), Vec2::splat(10.0), repeat, 100.0);
+        assert_eq!(transform.transform_point2(Vec2::ONE), repeat);
+    }
+}
@@ -0,0 +1,36 @@
+// systems/texture_library.rs
+// Copyright (C) 2026 vecnode
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use crate::components::{ImageSequenceState, TextureLibrary, TextureLibraryEntry, TextureSourceState};
+
+pub fn track_texture_library(
+    texture_source: Res<TextureSourceState>,
+    sequence_state: Res<ImageSequenceState>,
+    mut library: ResMut<TextureLibrary>,
+) {
+    if !texture_source.is_changed() {
+        return;
+    }
+    let Some(handle) = &texture_source.current else {
+        return;
+    };
+    // Sequence frames are owned by the sequence player and would flood the library
+    if sequence_state.contains(handle) || library.entries.iter().any(|entry| entry.handle == *handle) {
+        return;
+    }
+
+    let label = Path::new(&texture_source.path)
+        .file_name()
+        .map_or_else(|| texture_source.path.clone(), |name| name.to_string_lossy().into_owned());
+
+    // The library keeps a strong handle so switching back never reloads from disk
+    library.entries.push(TextureLibraryEntry {
+        label,
+        path: texture_source.path.clone(),
+        file_path: texture_source.file_path.clone(),
+        handle: handle.clone(),
+    });
+}
@@ -0,0 +1,92 @@
+// systems/texture_source.rs
+// Copyright (C) 2026 vecnode
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use crate::components::{EguiLayoutState, ImageSequenceState, TextureSourceState, ToastState};
+use crate::constants::SUPPORTED_IMAGE_EXTENSIONS;
+use crate::systems::picking::cursor_in_viewport;
+
+pub fn is_supported_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            SUPPORTED_IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        })
+}
+
+pub fn decode_image_bytes(bytes: &[u8], extension: &str) -> Result<Image, String> {
+    Image::from_buffer(
+        bytes,
+        ImageType::Extension(extension),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        // Keep the pixels on the CPU side as well so they can be inspected and edited
+        RenderAssetUsages::default(),
+    )
+    .map_err(|error| error.to_string())
+}
+
+pub fn decode_image_file(path: &Path) -> Result<Image, String> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .ok_or_else(|| "file has no extension".to_string())?;
+    let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+    decode_image_bytes(&bytes, extension)
+}
+
+pub fn handle_dropped_files(
+    mut drop_events: MessageReader<FileDragAndDrop>,
+    window: Query<&Window>,
+    layout_state: Res<EguiLayoutState>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_source: ResMut<TextureSourceState>,
+    mut toast_state: ResMut<ToastState>,
+    mut sequence_state: ResMut<ImageSequenceState>,
+) {
+    for event in drop_events.read() {
+        let FileDragAndDrop::DroppedFile { window: window_entity, path_buf } = event else {
+            continue;
+        };
+
+        // Only accept drops on the 3D viewport. Some platforms don't report the cursor
+        // while dragging, in which case the drop is accepted anywhere in the window.
+        if let Ok(window) = window.get(*window_entity)
+            && window.cursor_position().is_some()
+            && cursor_in_viewport(window, &layout_state).is_none()
+        {
+            continue;
+        }
+
+        let file_name = path_buf.file_name().map_or_else(
+            || path_buf.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+
+        if !is_supported_image(path_buf) {
+            toast_state.show_error(format!("{file_name} is not a supported image"));
+            continue;
+        }
+
+        match decode_image_file(path_buf) {
+            Ok(image) => {
+                sequence_state.clear();
+                texture_source.set_file(path_buf, images.add(image));
+            }
+            Err(error) => {
+                toast_state.show_error(format!("Could not decode {file_name}: {error}"));
+            }
+        }
+    }
+}
+
+pub fn tick_toast(time: Res<Time>, mut toast_state: ResMut<ToastState>) {
+    if toast_state.message.is_some() && toast_state.timer.tick(time.delta()).is_finished() {
+        toast_state.message = None;
+    }
+}
@@ -0,0 +1,170 @@
+// systems/texture_view.rs
+// Copyright (C) 2026 vecnode
+
+use bevy::prelude::*;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy_egui::{egui, EguiContexts};
+use crate::components::{CompareMode, CompareState, EguiLayoutState, ImageTransformState, PlaneSize, RightCamera, ScaleCalibrationState, TextureViewDrag, TextureViewDragState, TextureViewTransform, TexturedPlane};
+use crate::constants::{ROTATE_HANDLE_OFFSET, ROTATE_HANDLE_RADIUS, VIEW_ZOOM_MAX, VIEW_ZOOM_MIN, VIEW_ZOOM_STEP};
+use crate::systems::picking::{cursor_in_viewport, pick_plane, plane_point_to_world, PlaneHit};
+
+// Angle of a plane point around the texture's center, for the rotate handle
+fn angle_around(view: &TextureViewTransform, point: Vec2) -> f32 {
+    let offset = point - view.pan;
+    offset.y.atan2(offset.x)
+}
+
+fn distance_to_segment(point: egui::Pos2, start: egui::Pos2, end: egui::Pos2) -> f32 {
+    let segment = end - start;
+    let t = ((point - start).dot(segment) / segment.length_sq().max(f32::EPSILON)).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn texture_view_interaction(
+    mut contexts: EguiContexts,
+    window: Query<&Window>,
+    layout_state: Res<EguiLayoutState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut wheel_events: MessageReader<MouseWheel>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<RightCamera>>,
+    mut plane_query: Query<(&GlobalTransform, &PlaneSize, &mut TextureViewTransform), With<TexturedPlane>>,
+    mut drag_state: ResMut<TextureViewDragState>,
+    calibration: Res<ScaleCalibrationState>,
+    transform_state: Res<ImageTransformState>,
+    mut compare_state: ResMut<CompareState>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let (Ok(window), Ok((camera, camera_transform))) = (window.single(), camera_query.single()) else {
+        return;
+    };
+    let Ok((plane_transform, plane_size, mut view)) = plane_query.single_mut() else {
+        return;
+    };
+
+    // The rotate handle sits on a stem above the top edge of the panned, zoomed and rotated texture
+    let placement = view.placement();
+    let top_edge = placement.transform_point2(Vec2::new(0.0, -0.5 * plane_size.0.y));
+    let handle_direction = Vec2::from_angle(view.rotation).rotate(Vec2::NEG_Y);
+    let handle_point = top_edge + handle_direction * ROTATE_HANDLE_OFFSET;
+    let to_screen = |point: Vec2| {
+        camera
+            .world_to_viewport(camera_transform, plane_point_to_world(plane_transform, point))
+            .ok()
+            .map(|position| egui::pos2(position.x, position.y))
+    };
+    let handle_screen = to_screen(handle_point);
+
+    let viewport = egui::Rect::from_min_max(
+        egui::pos2(layout_state.viewport_left, layout_state.viewport_top),
+        egui::pos2(layout_state.viewport_right, layout_state.viewport_bottom),
+    );
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("texture_view_handle")))
+        .with_clip_rect(viewport);
+
+    // The compare split is a vertical line across the plane itself, independent of the view
+    let split_screen = if compare_state.current.mode == CompareMode::Split && compare_state.current.texture_b.is_some() {
+        let split_x = (compare_state.current.split - 0.5) * plane_size.0.x;
+        let half_height = 0.5 * plane_size.0.y;
+        to_screen(Vec2::new(split_x, -half_height)).zip(to_screen(Vec2::new(split_x, half_height)))
+    } else {
+        None
+    };
+    if let Some((split_top, split_bottom)) = split_screen {
+        let active = matches!(drag_state.active, Some(TextureViewDrag::CompareSplit));
+        let color = if active { egui::Color32::WHITE } else { egui::Color32::from_gray(200) };
+        painter.line_segment([split_top, split_bottom], egui::Stroke::new(2.0, color));
+        painter.circle(
+            split_top.lerp(split_bottom, 0.5),
+            ROTATE_HANDLE_RADIUS,
+            egui::Color32::from_black_alpha(160),
+            egui::Stroke::new(1.5, color),
+        );
+    }
+
+    if let (Some(top_screen), Some(handle_screen)) = (to_screen(top_edge), handle_screen) {
+        let active = matches!(drag_state.active, Some(TextureViewDrag::Rotate { .. }));
+        let color = if active { egui::Color32::WHITE } else { egui::Color32::from_gray(180) };
+        painter.line_segment([top_screen, handle_screen], egui::Stroke::new(1.5, color));
+        painter.circle(
+            handle_screen,
+            ROTATE_HANDLE_RADIUS,
+            egui::Color32::from_black_alpha(160),
+            egui::Stroke::new(1.5, color),
+        );
+    }
+
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        drag_state.active = None;
+    }
+
+    // Leave the pointer to egui when it's over a panel or window
+    let pointer_free = !ctx.is_pointer_over_area() && !ctx.is_using_pointer();
+    let cursor = cursor_in_viewport(window, &layout_state).filter(|_| pointer_free);
+    let hit = window
+        .cursor_position()
+        .and_then(|cursor| pick_plane(camera, camera_transform, cursor, plane_transform, plane_size.0));
+
+    // Clicks place calibration points while calibrating, and draw the crop rectangle while cropping
+    if mouse_buttons.just_pressed(MouseButton::Left)
+        && !calibration.picking
+        && !transform_state.drawing_crop
+        && let (Some(cursor), Some(hit)) = (cursor, &hit)
+    {
+        let cursor = egui::pos2(cursor.x, cursor.y);
+        let on_handle = handle_screen.is_some_and(|handle| handle.distance(cursor) <= ROTATE_HANDLE_RADIUS + 2.0);
+        let on_split = split_screen
+            .is_some_and(|(top, bottom)| distance_to_segment(cursor, top, bottom) <= ROTATE_HANDLE_RADIUS);
+        if on_split {
+            drag_state.active = Some(TextureViewDrag::CompareSplit);
+        } else if on_handle {
+            drag_state.active = Some(TextureViewDrag::Rotate {
+                start_angle: angle_around(&view, hit.point),
+                start_rotation: view.rotation,
+            });
+        } else if hit.is_inside() {
+            drag_state.active = Some(TextureViewDrag::Pan { last_point: hit.point });
+        }
+    }
+
+    if let (Some(drag), Some(hit)) = (drag_state.active, &hit) {
+        match drag {
+            TextureViewDrag::Pan { last_point } => {
+                if hit.point != last_point {
+                    view.pan += hit.point - last_point;
+                    drag_state.active = Some(TextureViewDrag::Pan { last_point: hit.point });
+                }
+            }
+            TextureViewDrag::Rotate { start_angle, start_rotation } => {
+                let rotation = start_rotation + angle_around(&view, hit.point) - start_angle;
+                view.set_if_neq(TextureViewTransform { rotation, ..*view });
+            }
+            TextureViewDrag::CompareSplit => {
+                let split = hit.uv.x.clamp(0.0, 1.0);
+                if compare_state.current.split != split {
+                    compare_state.current.split = split;
+                }
+            }
+        }
+    }
+
+    // Scroll zooms around the point under the cursor
+    let scroll_lines: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+    if scroll_lines != 0.0
+        && cursor.is_some()
+        && let Some(hit) = hit.filter(PlaneHit::is_inside)
+    {
+        let zoom = (view.zoom * VIEW_ZOOM_STEP.powf(scroll_lines)).clamp(VIEW_ZOOM_MIN, VIEW_ZOOM_MAX);
+        let pan = hit.point - (hit.point - view.pan) * (zoom / view.zoom);
+        view.set_if_neq(TextureViewTransform { pan, zoom, ..*view });
+    }
+}